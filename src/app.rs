@@ -1,9 +1,22 @@
-use bevy::app::SubApp;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bevy::app::AppExit;
+#[cfg(feature = "render")]
+use bevy::app::{AppLabel, SubApp};
 use bevy::ecs::schedule::ScheduleLabel;
+use bevy::ecs::system::{RunSystemOnce, SystemParam};
+use bevy::ecs::world::WorldId;
 use bevy::prelude::*;
-use bevy::render::pipelined_rendering::RenderExtractApp;
+#[cfg(feature = "render")]
+use bevy::render::render_resource::PipelineCache;
+#[cfg(feature = "render")]
 use bevy::render::RenderApp;
-use bevy::time::{TimeReceiver, TimeSender};
+use bevy::time::{TimeReceiver, TimeSender, TimeUpdateStrategy};
+use bevy::utils::Instant;
+#[cfg(feature = "winit")]
+use bevy::winit::WinitWindows;
 
 use crate::*;
 
@@ -28,60 +41,537 @@ pub enum SwapCommand
     Pass(WorldSwapApp),
     /// Swap in another app's world and put the current world in the background.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if there is already a world in the background.
+    /// Fails with [`SwapErrorReason::BackgroundOccupied`] if there is already a world in the background.
     Fork(WorldSwapApp),
     /// Swap in the background world and put the current world in the background.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if there is no world in the background.
+    /// Fails with [`SwapErrorReason::NoBackgroundWorld`] if there is no world in the background.
     Swap,
     /// Swap in the background world and drop the current world.
     ///
     /// Note that if the background world sent `AppExit` at any point in the past, then as soon as it enters the
     /// foreground the app will shut down.
     ///
-    /// # Panics
+    /// # Errors
+    ///
+    /// Fails with [`SwapErrorReason::NoBackgroundWorld`] if there is no world in the background.
+    Join,
+    /// Shut down the app with the given [`AppExit`].
+    ///
+    /// The background world will be dropped (or recovered with [`WorldSwapPlugin::abort_recovery`]), and the
+    /// `AppExit` will be sent to the foreground world so the event loop exits.
+    AbortAll(AppExit),
+    /// Run the background world's `Main` schedule the given number of times, even if its [`BackgroundTickRate`]
+    /// would not update it.
+    ///
+    /// Each step advances the background world's time by the foreground world's last frame time, even if its clock
+    /// is paused. This is useful for single-stepping a paused world from a debug menu. Worlds are not swapped.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`SwapErrorReason::NoBackgroundWorld`] if there is no world in the background.
+    StepBackground(u32),
+    /// Advance the background world's time by the given duration in a single update of its `Main` schedule, even
+    /// if its [`BackgroundTickRate`] would not update it.
+    ///
+    /// The world's clock is advanced even if it is paused, and [`Time<Virtual>::max_delta`] is raised for the
+    /// update so the full duration is simulated. Fixed-timestep schedules catch up in that update. This is useful
+    /// for fast-forwarding a paused world from the foreground. Worlds are not swapped.
+    ///
+    /// # Errors
     ///
-    /// Panics if there is no world in the background.
+    /// Fails with [`SwapErrorReason::NoBackgroundWorld`] if there is no world in the background.
+    AdvanceBackground(Duration),
+    /// Run a callback in the `bevy_worldswap` backend.
+    ///
+    /// This is an escape hatch for bespoke swap behavior. See [`CustomSwapFn`]. Worlds are not swapped by the
+    /// backend, so the command is applied or fails according to the callback's result.
+    ///
+    /// Custom commands are not subject to [`WorldSwapPlugin::command_arbitration`]. All custom commands received
+    /// in a tick are applied in the order they were sent, before the tick's arbitrated command.
+    Custom(CustomSwapFn),
+}
+
+/// Callback for [`SwapCommand::Custom`].
+///
+/// The callback runs in the backend's [`SwapStage::ApplySwap`] stage with access to the foreground and background
+/// worlds. It returns the command's outcome: `Ok` is reported with [`SwapApplied`], and `Err` is reported with a
+/// [`SwapError`] that has the returned reason.
+///
+/// The callback must not swap worlds (e.g. by exchanging the foreground world with the background world). The
+/// backend won't update its [`WorldRegistry`] or window state for a swap it doesn't know about.
+pub type CustomSwapFn = Box<dyn FnOnce(CustomSwapWorlds<'_>) -> Result<(), SwapErrorReason> + Send>;
+
+/// The worlds a [`CustomSwapFn`] can access.
+pub struct CustomSwapWorlds<'a>
+{
+    /// The foreground world.
+    pub foreground: &'a mut World,
+    /// The background world, if there is one.
+    pub background: Option<&'a mut World>,
+}
+
+impl SwapCommand
+{
+    /// Gets the command's [`SwapCommandKind`].
+    pub fn kind(&self) -> SwapCommandKind
+    {
+        match self {
+            Self::Pass(_) => SwapCommandKind::Pass,
+            Self::Fork(_) => SwapCommandKind::Fork,
+            Self::Swap => SwapCommandKind::Swap,
+            Self::Join => SwapCommandKind::Join,
+            Self::AbortAll(_) => SwapCommandKind::AbortAll,
+            Self::StepBackground(_) => SwapCommandKind::StepBackground,
+            Self::AdvanceBackground(_) => SwapCommandKind::AdvanceBackground,
+            Self::Custom(_) => SwapCommandKind::Custom,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Identifies the kind of a [`SwapCommand`] without its contents.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SwapCommandKind
+{
+    Pass,
+    Fork,
+    Swap,
     Join,
+    AbortAll,
+    StepBackground,
+    AdvanceBackground,
+    Custom,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Optional behavior applied when a [`SwapCommand`] swaps worlds.
+///
+/// Defaults are set with [`WorldSwapPlugin::swap_behavior`] and can be overridden per command with
+/// [`SwapOptions`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SwapBehavior
+{
+    /// Render the outgoing foreground world one last time before it leaves the foreground.
+    ///
+    /// Disable this for swaps that should add no extra latency (e.g. a quick pause menu).
+    ///
+    /// True by default.
+    pub render_final_frame: bool,
+    /// Send window events that the incoming world missed while it wasn't in the foreground.
+    ///
+    /// True by default.
+    pub replay_window_events: bool,
+    /// Wait until after the incoming world's first update in the foreground to replay missed window events.
+    ///
+    /// This keeps the swap tick short, but the incoming world will see stale window state for one update.
+    ///
+    /// False by default.
+    pub defer_window_event_replay: bool,
+    /// Reset keyboard, mouse, and gamepad button input in the incoming world.
+    ///
+    /// This prevents keys held during a swap from being seen as still pressed by the incoming world.
+    ///
+    /// False by default.
+    pub reset_input: bool,
+}
+
+impl Default for SwapBehavior
+{
+    fn default() -> Self
+    {
+        Self {
+            render_final_frame: true,
+            replay_window_events: true,
+            defer_window_event_replay: false,
+            reset_input: false,
+        }
+    }
+}
+
+impl From<SwapLatencyMode> for SwapBehavior
+{
+    fn from(mode: SwapLatencyMode) -> Self
+    {
+        let options = SwapOptions::from(mode);
+        options.resolve(Self::default())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Presets for trading swap latency against presentation smoothness.
+///
+/// Convert into [`SwapBehavior`] for [`WorldSwapPlugin::swap_behavior`], or into [`SwapOptions`] for individual
+/// commands. Only the fields that affect latency are set.
+///
+/// The render worker hand-off is the same in both modes: the incoming world always waits for the previous world's
+/// renderer to finish its current frame, since two renderers can't present to the same windows at once.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SwapLatencyMode
+{
+    /// Skip the outgoing world's final frame and replay missed window events right away.
+    ///
+    /// The incoming world is shown one frame sooner, but the outgoing world's last visual changes may pop.
+    MinimalLatency,
+    /// Render the outgoing world's final frame and defer replaying missed window events.
+    SmoothPresentation,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Per-command overrides for [`SwapBehavior`].
+///
+/// Use with [`SwapCommandSender::send_with`]. Fields set to `None` use the default from
+/// [`WorldSwapPlugin::swap_behavior`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct SwapOptions
+{
+    /// Overrides [`SwapBehavior::render_final_frame`].
+    pub render_final_frame: Option<bool>,
+    /// Overrides [`SwapBehavior::replay_window_events`].
+    pub replay_window_events: Option<bool>,
+    /// Overrides [`SwapBehavior::defer_window_event_replay`].
+    pub defer_window_event_replay: Option<bool>,
+    /// Overrides [`SwapBehavior::reset_input`].
+    pub reset_input: Option<bool>,
+}
+
+impl SwapOptions
+{
+    /// Applies these overrides to a default [`SwapBehavior`].
+    pub fn resolve(&self, defaults: SwapBehavior) -> SwapBehavior
+    {
+        SwapBehavior {
+            render_final_frame: self.render_final_frame.unwrap_or(defaults.render_final_frame),
+            replay_window_events: self.replay_window_events.unwrap_or(defaults.replay_window_events),
+            defer_window_event_replay: self
+                .defer_window_event_replay
+                .unwrap_or(defaults.defer_window_event_replay),
+            reset_input: self.reset_input.unwrap_or(defaults.reset_input),
+        }
+    }
+}
+
+impl From<SwapLatencyMode> for SwapOptions
+{
+    fn from(mode: SwapLatencyMode) -> Self
+    {
+        let (render_final_frame, defer_window_event_replay) = match mode {
+            SwapLatencyMode::MinimalLatency => (false, false),
+            SwapLatencyMode::SmoothPresentation => (true, true),
+        };
+        Self {
+            render_final_frame: Some(render_final_frame),
+            defer_window_event_replay: Some(defer_window_event_replay),
+            ..default()
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Identifies a [`SwapCommand`] sent with [`SwapCommandSender::send`].
+///
+/// Ids are unique within an app. They are included in [`SwapApplied`] and [`SwapError`] events so commands can be
+/// correlated with their outcomes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SwapCommandId(pub(crate) u64);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Where a [`SwapCommand`] came from.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct SwapOrigin
+{
+    /// The sending world, if known.
+    pub(crate) source: Option<WorldId>,
+    pub(crate) id: SwapCommandId,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A [`SwapCommand`] tagged with its origin.
+pub(crate) struct SwapRequest
+{
+    pub(crate) origin: SwapOrigin,
+    pub(crate) command: SwapCommand,
+    pub(crate) options: SwapOptions,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Resource for sending [`SwapCommands`](SwapCommand).
 ///
-/// Only the last swap command sent during a tick will be applied. If a foreground and background world send
-/// commands in the same tick, then the foreground command will take precedence.
+/// Only one swap command is applied per tick. If several are sent in the same tick, then
+/// [`WorldSwapPlugin::command_arbitration`] decides which one is applied. [`SwapCommand::Custom`] commands are the
+/// exception, since they don't swap worlds they are all applied.
+///
+/// When a command is applied, a [`SwapApplied`] event is sent to the world that owns this sender. If a command
+/// fails, a [`SwapError`] event is sent instead. Use [`ValidatedSwapCommands`] to catch some failures before
+/// sending.
 #[derive(Resource, Clone)]
-pub struct SwapCommandSender(pub(crate) crossbeam::channel::Sender<SwapCommand>);
+pub struct SwapCommandSender
+{
+    pub(crate) sender: BoundedSender<SwapRequest>,
+    /// Reports commands dropped by the channel's [`OverflowPolicy`] to the backend.
+    dropped: crossbeam::channel::Sender<DroppedSwapCommand>,
+    pub(crate) source: Option<WorldId>,
+    next_id: Arc<AtomicU64>,
+}
 
 impl SwapCommandSender
 {
+    pub(crate) fn new(
+        sender: BoundedSender<SwapRequest>,
+        dropped: crossbeam::channel::Sender<DroppedSwapCommand>,
+    ) -> Self
+    {
+        Self { sender, dropped, source: None, next_id: Arc::new(AtomicU64::new(0)) }
+    }
+
+    /// Makes a sender on the same channel for a different source world.
+    pub(crate) fn with_source(&self, source: Option<WorldId>) -> Self
+    {
+        Self {
+            sender: self.sender.clone(),
+            dropped: self.dropped.clone(),
+            source,
+            next_id: self.next_id.clone(),
+        }
+    }
+
     /// Sends a [`SwapCommand`] to the `bevy_worldswap` backend.
-    pub fn send(&self, command: SwapCommand)
+    ///
+    /// Returns the command's id.
+    pub fn send(&self, command: SwapCommand) -> SwapCommandId
+    {
+        self.send_with(command, SwapOptions::default())
+    }
+
+    /// Sends a [`SwapCommand`] to the `bevy_worldswap` backend with [`SwapOptions`] that override the default
+    /// [`SwapBehavior`].
+    ///
+    /// Returns the command's id.
+    pub fn send_with(&self, command: SwapCommand, options: SwapOptions) -> SwapCommandId
+    {
+        let id = SwapCommandId(self.next_id.fetch_add(1, Ordering::Relaxed));
+
+        // Report dropped commands so the sending world gets a SwapError.
+        // - The dropped command may be an older command if the channel drops the oldest message.
+        let origin = SwapOrigin { source: self.source, id };
+        match self.sender.send(SwapRequest { origin, command, options }) {
+            Ok(()) | Err(SendFailure::Disconnected) => (),
+            Err(SendFailure::Dropped(request)) => {
+                let dropped = DroppedSwapCommand { origin: request.origin, kind: request.command.kind() };
+                let _ = self.dropped.send(dropped);
+            }
+        }
+
+        id
+    }
+
+    /// Runs a one-shot system in the foreground world.
+    ///
+    /// The system runs with [`RunSystemOnce`] when the command is applied, after the foreground world's `Main`
+    /// schedule. This is sent as a [`SwapCommand::Custom`], so it skips [`WorldSwapPlugin::command_arbitration`]
+    /// and runs before any swap applied in the same tick.
+    ///
+    /// Returns the command's id.
+    pub fn run_in_foreground<M>(&self, system: impl IntoSystem<(), (), M> + Send + 'static) -> SwapCommandId
+    {
+        self.send(SwapCommand::Custom(Box::new(move |worlds| {
+            worlds.foreground.run_system_once(system);
+            Ok(())
+        })))
+    }
+
+    /// Runs a one-shot system in the background world.
+    ///
+    /// The system runs with [`RunSystemOnce`] when the command is applied, before the background world's next
+    /// update. If there is no background world then the system is dropped and the command fails with
+    /// [`SwapErrorReason::NoBackgroundWorld`]. This is sent as a [`SwapCommand::Custom`], so it skips
+    /// [`WorldSwapPlugin::command_arbitration`] and runs before any swap applied in the same tick.
+    ///
+    /// Returns the command's id.
+    pub fn run_in_background<M>(&self, system: impl IntoSystem<(), (), M> + Send + 'static) -> SwapCommandId
+    {
+        self.send(SwapCommand::Custom(Box::new(move |worlds| {
+            let background = worlds.background.ok_or(SwapErrorReason::NoBackgroundWorld)?;
+            background.run_system_once(system);
+            Ok(())
+        })))
+    }
+
+    /// Returns `true` if any world sent a [`SwapCommand`] that the backend hasn't received yet.
+    ///
+    /// The backend receives commands after the foreground world's `Main` schedule. See [`no_swap_pending`].
+    pub fn has_pending(&self) -> bool
+    {
+        !self.sender.is_empty()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// System parameter for sending [`SwapCommands`](SwapCommand) that are checked before they are sent.
+///
+/// Commands that would fail are returned in a [`SwapSendError`] instead of being sent to the backend. Checks use
+/// the world's [`WorldSwapStatus`] and [`WorldRegistry`], which are updated after each swap.
+///
+/// Some failures can only be detected by the backend (e.g. an incoming world that is missing window plumbing, or
+/// a command that is superseded by another command sent in the same tick), so [`SwapError`] events are still
+/// possible.
+#[derive(SystemParam)]
+pub struct ValidatedSwapCommands<'w>
+{
+    sender: Res<'w, SwapCommandSender>,
+    status: Res<'w, WorldSwapStatus>,
+    registry: Res<'w, WorldRegistry>,
+}
+
+impl ValidatedSwapCommands<'_>
+{
+    /// Sends a [`SwapCommand`] to the `bevy_worldswap` backend if it is valid.
+    ///
+    /// See [`SwapCommandSender::send`].
+    pub fn send(&self, command: SwapCommand) -> Result<SwapCommandId, SwapSendError>
+    {
+        self.send_with(command, SwapOptions::default())
+    }
+
+    /// Sends a [`SwapCommand`] to the `bevy_worldswap` backend with [`SwapOptions`] if it is valid.
+    ///
+    /// See [`SwapCommandSender::send_with`].
+    pub fn send_with(&self, command: SwapCommand, options: SwapOptions) -> Result<SwapCommandId, SwapSendError>
+    {
+        if *self.status == WorldSwapStatus::Suspended {
+            return Err(SwapSendError::Suspended(command));
+        }
+        if let Some(reason) = self.check(&command) {
+            return Err(SwapSendError::Invalid(command, reason));
+        }
+
+        Ok(self.sender.send_with(command, options))
+    }
+
+    fn check(&self, command: &SwapCommand) -> Option<SwapErrorReason>
     {
-        // Ignore errors.
-        let _ = self.0.send(command);
+        let has_background = self.registry.background().is_some();
+        match command {
+            SwapCommand::Fork(_) if has_background => Some(SwapErrorReason::BackgroundOccupied),
+            SwapCommand::Pass(new_app) | SwapCommand::Fork(new_app) => {
+                // The new world's swap commands must come to this backend.
+                let sender = new_app.world.get_resource::<SwapCommandSender>()?;
+                let foreign = !sender.sender.same_channel(&self.sender.sender);
+                foreign.then_some(SwapErrorReason::ForeignSwapCommandSender)
+            }
+            SwapCommand::Swap
+            | SwapCommand::Join
+            | SwapCommand::StepBackground(_)
+            | SwapCommand::AdvanceBackground(_)
+                if !has_background =>
+            {
+                Some(SwapErrorReason::NoBackgroundWorld)
+            }
+            _ => None,
+        }
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Event sent to the world that sent a [`SwapCommand`] when the command is applied.
+///
+/// If the sending world was dropped by the command (e.g. [`SwapCommand::Join`]), then no event is sent.
+#[derive(Event, Debug, Copy, Clone)]
+pub struct SwapApplied
+{
+    /// The command's id.
+    pub id: SwapCommandId,
+    /// The kind of command that was applied.
+    pub command: SwapCommandKind,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event sent when a [`SwapCommand::Pass`] or [`SwapCommand::Fork`] brings in a world that contains unread
+/// [`AppExit`] events.
+///
+/// Those events will end the incoming world as soon as it enters the foreground (or join it back to the background
+/// world). This usually means a suspended world that already exited was re-used. Call
+/// [`WorldSwapApp::clear_exit`] before re-using a world.
+///
+/// The event is sent to the same world that would receive a [`SwapError`] for the command. The command is still
+/// applied.
+#[derive(Event, Debug, Copy, Clone)]
+pub struct StaleAppExit
+{
+    /// The command's id.
+    pub id: SwapCommandId,
+    /// The kind of command that brought in the world.
+    pub command: SwapCommandKind,
+    /// The incoming world.
+    pub world: WorldId,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event sent to the foreground world after the background world's `Main` schedule runs.
+///
+/// Lets foreground logic and telemetry observe the background world without accessing it. No event is sent on
+/// ticks where the background world doesn't update (e.g. with [`BackgroundTickRate::Never`]).
+#[derive(Event, Debug, Clone)]
+pub struct BackgroundTickReport
+{
+    /// The background world.
+    pub world: WorldId,
+    /// The background world's label, if it has one.
+    pub label: Option<WorldLabel>,
+    /// Wall time spent in the update.
+    pub duration: Duration,
+    /// Whether the background world has unread `AppExit` events after the update.
+    pub sent_exit: bool,
+    /// Whether swap commands were sent during the update.
+    pub sent_swap_commands: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Resource for receiving [`SwapCommands`](SwapCommand).
 ///
 /// Only used in [`WorldSwapSubApp`].
 #[derive(Resource, Deref)]
-pub(crate) struct SwapCommandReceiver(pub(crate) crossbeam::channel::Receiver<SwapCommand>);
+pub(crate) struct SwapCommandReceiver(pub(crate) BoundedReceiver<SwapRequest>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A [`SwapCommand`] that was dropped because the swap command channel was full.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct DroppedSwapCommand
+{
+    pub(crate) origin: SwapOrigin,
+    pub(crate) kind: SwapCommandKind,
+}
+
+/// Resource for receiving [`DroppedSwapCommands`](DroppedSwapCommand).
+///
+/// Only used in [`WorldSwapSubApp`].
+#[derive(Resource, Deref)]
+pub(crate) struct DroppedSwapCommandReceiver(pub(crate) crossbeam::channel::Receiver<DroppedSwapCommand>);
 
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Resource that records the world-swap status of a world.
 ///
 /// This is controlled by the `bevy_worldswap` backend.
-#[derive(Resource, Copy, Clone, Eq, PartialEq)]
+#[derive(Resource, Debug, Copy, Clone, Eq, PartialEq)]
 pub enum WorldSwapStatus
 {
     /// The world is suspended.
@@ -97,11 +587,27 @@ pub enum WorldSwapStatus
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Resource that records how much real time a world spent away from the foreground.
+///
+/// Updated by the `bevy_worldswap` backend when the world re-enters the foreground. Time spent suspended and in
+/// the background both count. Time before a world first enters the foreground does not count.
+///
+/// Useful for 'you were away for 3 minutes' logic (e.g. in a system that runs on [`entered_foreground`]).
+#[derive(Resource, Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct TimeAwayFromForeground
+{
+    /// How long the world was away the last time it left the foreground.
+    pub last: Duration,
+    /// Total time the world has spent away from the foreground.
+    pub total: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Stores a [`World`] that is not in the foreground.
 ///
 /// The world might be [`Suspended`](WorldSwapStatus::Suspended) or in the
 /// [`Background`](WorldSwapStatus::Background).
-//todo: configure with bevy_render flag
 pub struct WorldSwapApp
 {
     /// The stored world.
@@ -114,19 +620,32 @@ pub struct WorldSwapApp
     ///
     /// If this is true, then the world will be unpaused when swapped into the foreground.
     pub(crate) paused_by_tick_policy: bool,
-    /// Receives time from this world's [`RenderApp`].
+    /// Records when the world was paused due to BackgroundTickRate::Never::freeze_time.
+    ///
+    /// Used to compute [`FixedCatchUp`] steps when the world is unpaused.
+    pub(crate) paused_at: Option<Instant>,
+    /// Receives time from this world's `RenderApp`.
     ///
     /// Cached while the world is away from the foreground so its internal time will increment properly. Normally,
-    /// worlds that render will have their time sent from [`RenderApp`].
+    /// worlds that render will have their time sent from their `RenderApp`.
     pub(crate) time_receiver: Option<TimeReceiver>,
     /// Sends time to this world.
     ///
     /// Cached so that time can be sent while in the foreground when not rendering while waiting for the previous
-    /// world to finish rendering.
+    /// world to finish rendering, and while the world updates in the background.
     pub(crate) time_sender: Option<TimeSender>,
-    /// The world's [`RenderApp`] or [`RenderExtractApp`].
+    /// Number of times to run the world's [`Main`] schedule before it first enters the foreground.
+    pub(crate) warmup_ticks: u32,
+    /// The world's [`TimeUpdateStrategy`], cached while the world is in [`BackgroundTickRate::Lockstep`].
+    pub(crate) time_update_strategy: Option<TimeUpdateStrategy>,
+    /// When the world's schedule last ran with [`BackgroundTickRate::Schedule`].
+    pub(crate) last_schedule_run: Option<Instant>,
+    /// When the world last left the foreground. See [`TimeAwayFromForeground`].
+    pub(crate) left_foreground_at: Option<Instant>,
+    /// The world's render subapp (see [`RenderSubAppLabels`]).
     ///
     /// Cached while the world is away from the foreground.
+    #[cfg(feature = "render")]
     pub(crate) render_app: Option<SubApp>,
 }
 
@@ -136,32 +655,77 @@ impl WorldSwapApp
     ///
     /// This method calls [`App::finish`] and [`App::cleanup`] on the app before removing its contents.
     ///
+    /// Windowed apps that are missing the crate's window plumbing (e.g. because they use a hand-rolled plugin list
+    /// instead of [`ChildDefaultPlugins`]) will have it added automatically.
+    ///
     /// The app will have the default background tick rate configured in [`WorldSwapPlugin`]. Use
     /// [`Self::new_with`] if you want a specific tick rate for this app.
     ///
     /// ## Panics
     /// - If the app's [`main_schedule_label`](App::main_schedule_label) is not [`Main`].
-    pub fn new(mut app: App) -> Self
+    /// - If the app contains [`WorldSwapPlugin`].
+    ///
+    /// See [`Self::try_new`].
+    pub fn new(app: App) -> Self
+    {
+        Self::try_new(app).unwrap_or_else(|err| panic!("failed making WorldSwapApp: {err}"))
+    }
+
+    /// Creates a new world-swap wrapper for a fresh [`App`], or returns an error if the app can't be used as a
+    /// child app.
+    ///
+    /// See [`Self::new`].
+    pub fn try_new(mut app: App) -> Result<Self, WorldSwapAppError>
     {
         if app.main().update_schedule != Some(Main.intern()) {
-            panic!("failed making WorldSwapApp, app's main_schedule_label is not Main");
+            return Err(WorldSwapAppError::MainScheduleNotMain);
+        }
+        // Check this before finishing the app, since WorldSwapPlugin will take the app's render subapp when it is
+        // cleaned up.
+        if app.is_plugin_added::<WorldSwapPlugin>() || app.get_sub_app(WorldSwapSubApp).is_some() {
+            return Err(WorldSwapAppError::HasWorldSwapPlugin);
         }
-        app.insert_resource(WorldSwapStatus::Suspended);
+        // Install plumbing that hand-rolled plugin lists may have missed.
+        // - Windowed worlds need to cache window events while in the foreground, otherwise other worlds won't
+        //   receive them.
+        #[cfg(feature = "winit")]
+        if app.world().contains_non_send::<WinitWindows>() && !app.is_plugin_added::<WorldSwapWindowPlugin>() {
+            tracing::debug!("adding missing window plumbing to child app");
+            app.add_plugins(WorldSwapWindowPlugin);
+        }
+        app.insert_resource(WorldSwapStatus::Suspended)
+            .init_resource::<TimeAwayFromForeground>()
+            .add_event::<SwapApplied>()
+            .add_event::<SwapError>()
+            .add_event::<StaleAppExit>()
+            .add_event::<BackgroundTickReport>()
+            .add_event::<AttentionRequest>();
         app.finish();
         app.cleanup();
+        // Window entities are inserted when the world enters the foreground, so prewarm their archetypes now.
+        // - This is done after the app is finished so plugins have set up the world first.
+        #[cfg(feature = "winit")]
+        if app.world().contains_non_send::<WinitWindows>() {
+            prewarm_window_archetypes(app.world_mut());
+        }
         let time_receiver = app.world_mut().remove_resource::<TimeReceiver>();
         let time_sender = app.world_mut().remove_resource::<TimeSender>();
-        let render_app = app
-            .remove_sub_app(RenderApp)
-            .or_else(|| app.remove_sub_app(RenderExtractApp));
-        Self {
+        #[cfg(feature = "render")]
+        let render_app = remove_render_subapp(&mut app);
+        Ok(Self {
             world: std::mem::take(app.world_mut()),
             background_tick_rate: None,
             paused_by_tick_policy: false,
+            paused_at: None,
             time_receiver,
             time_sender,
+            time_update_strategy: None,
+            last_schedule_run: None,
+            left_foreground_at: None,
+            warmup_ticks: 0,
+            #[cfg(feature = "render")]
             render_app,
-        }
+        })
     }
 
     /// Creates a new world-swap wrapper for a fresh [`App`] with a specific [`BackgroundTickRate`].
@@ -173,6 +737,169 @@ impl WorldSwapApp
         app.background_tick_rate = Some(background_tick_rate);
         app
     }
+
+    /// Creates a new world-swap wrapper for a fresh [`App`] with a [`WorldLabel`].
+    ///
+    /// The label is shown instead of the world's [`WorldId`](bevy::ecs::world::WorldId) in logs, the
+    /// [`WorldRegistry`], and [`WorldPanicInfo`].
+    ///
+    /// See [`Self::new`].
+    pub fn new_labeled(app: App, label: impl Into<WorldLabel>) -> Self
+    {
+        let mut app = Self::new(app);
+        app.world.insert_resource(label.into());
+        app
+    }
+
+    /// Returns `true` if the world contains unread [`AppExit`] events.
+    pub fn has_exit(&self) -> bool
+    {
+        self.world.get_resource::<Events<AppExit>>().is_some_and(|events| !events.is_empty())
+    }
+
+    /// Clears [`AppExit`] events from the world.
+    ///
+    /// Use this before re-using a world that exited (e.g. a world recovered by
+    /// [`WorldSwapPlugin::swap_join_recovery`]), otherwise it will exit again as soon as it enters the foreground.
+    /// See [`StaleAppExit`].
+    pub fn clear_exit(&mut self)
+    {
+        if let Some(mut events) = self.world.get_resource_mut::<Events<AppExit>>() {
+            events.clear();
+        }
+    }
+
+    /// Sets the seed of the world's [`WorldRng`].
+    ///
+    /// The generator is installed when the world first enters the foreground. See [`DeterminismPlugin`].
+    pub fn with_seed(mut self, seed: u64) -> Self
+    {
+        self.world.insert_resource(WorldSeed(seed));
+        self.world.remove_resource::<WorldRng>();
+        self
+    }
+
+    /// Requests a [`ForkSnapshot`] of the world's reflected resources when it is forked with
+    /// [`SwapCommand::Fork`].
+    ///
+    /// Use [`ResourceDiff::since_fork`] when the world joins to see which resources changed.
+    ///
+    /// Requires the `world_diff` feature.
+    #[cfg(feature = "world_diff")]
+    pub fn with_fork_snapshot(mut self) -> Self
+    {
+        self.world.init_resource::<TakeForkSnapshot>();
+        self
+    }
+
+    /// Gets the world's [`WorldLabel`], if it has one.
+    pub fn label(&self) -> Option<&WorldLabel>
+    {
+        self.world.get_resource::<WorldLabel>()
+    }
+
+    /// Sets the number of times the world's [`Main`] schedule will be run before the world enters the foreground
+    /// with [`SwapCommand::Pass`] or [`SwapCommand::Fork`].
+    ///
+    /// Warm-up ticks run without windows or rendering, which avoids showing a frame where `Startup` systems have
+    /// run but nothing else has happened yet.
+    pub fn with_warmup_ticks(mut self, ticks: u32) -> Self
+    {
+        self.warmup_ticks = ticks;
+        self
+    }
+
+    /// Sets whether the world should be rendered while in the foreground.
+    ///
+    /// This inserts or removes the [`RenderingDisabled`] resource, which can also be toggled at runtime.
+    #[cfg(feature = "render")]
+    pub fn with_rendering_disabled(mut self, disabled: bool) -> Self
+    {
+        if disabled {
+            self.world.insert_resource(RenderingDisabled);
+        } else {
+            self.world.remove_resource::<RenderingDisabled>();
+        }
+        self
+    }
+
+    /// Compiles render pipelines that were queued in the world's render app (e.g. by render plugins during
+    /// startup).
+    ///
+    /// Call this while another world is in the foreground to reduce stalls on shader compilation after the world
+    /// enters the foreground. Pipelines that are specialized for visible entities (e.g. materials) are queued only
+    /// when the world is rendered, so they won't be pre-compiled.
+    ///
+    /// Does nothing if the world has no render app.
+    ///
+    /// Requires the `render` feature.
+    #[cfg(feature = "render")]
+    pub fn prewarm_pipelines(&mut self)
+    {
+        let Some(render_app) = &mut self.render_app else { return };
+        let Some(mut pipeline_cache) = render_app.world_mut().get_resource_mut::<PipelineCache>() else { return };
+        pipeline_cache.process_queue();
+    }
+
+    /// Converts this back into a standalone [`App`].
+    ///
+    /// The world's render subapp and time channels are re-attached, so the app can be updated directly (e.g. to
+    /// re-host a world received in a [`SwapRecoveryFn`]). The app's runner is not restored.
+    ///
+    /// Note that the world will still contain resources inserted by the `bevy_worldswap` backend (e.g.
+    /// [`SwapCommandSender`] and [`WorldSwapStatus`]).
+    pub fn into_app(mut self) -> App
+    {
+        if let Some(strategy) = self.time_update_strategy.take() {
+            self.world.insert_resource(strategy);
+        }
+        if let Some(time_receiver) = self.time_receiver.take() {
+            self.world.insert_resource(time_receiver);
+        }
+        if let Some(time_sender) = self.time_sender.take() {
+            self.world.insert_resource(time_sender);
+        }
+
+        let mut app = App::empty();
+        app.main_mut().update_schedule = Some(Main.intern());
+        *app.world_mut() = self.world;
+
+        #[cfg(feature = "render")]
+        if let Some(mut render_app) = self.render_app.take() {
+            let label = render_app
+                .world_mut()
+                .remove_resource::<RenderSubAppLabel>()
+                .map(|label| label.0)
+                .unwrap_or(RenderApp.intern());
+            app.insert_sub_app(label, render_app);
+        }
+
+        app
+    }
+
+    /// Inserts a [`FixedCatchUp`] policy into the app's world.
+    pub fn with_fixed_catch_up(mut self, max_steps: u32) -> Self
+    {
+        self.world.insert_resource(FixedCatchUp { max_steps });
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that allows a world to catch up on [`FixedUpdate`] after its time was frozen in the background.
+///
+/// When a world frozen by [`BackgroundTickRate::Never`] (with `freeze_time = true`) leaves the background, it will
+/// run [`FixedMain`](bevy::app::FixedMain) once per fixed timestep of real time that elapsed while it was frozen,
+/// up to `max_steps` times. This is useful for simulations that expect wall-clock continuity (e.g. cooldowns).
+///
+/// Insert this resource into an app before constructing its [`WorldSwapApp`], or use
+/// [`WorldSwapApp::with_fixed_catch_up`].
+#[derive(Resource, Debug, Copy, Clone)]
+pub struct FixedCatchUp
+{
+    /// The maximum number of catch-up steps to run.
+    pub max_steps: u32,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
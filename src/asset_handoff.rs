@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Assets of type `T` waiting to be received, stored in the [`SharedStore`].
+struct PendingAssetHandoffs<T: Asset>
+{
+    assets: VecDeque<T>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// System parameter for handing assets from one world to another.
+///
+/// Assets are stored per-world, so a handle from one world can't be used in another. Call [`Self::send`] in the
+/// world that loaded an asset to move the asset out of its [`Assets<T>`], then call [`Self::receive`] in the
+/// target world to add the asset to that world's [`Assets<T>`] and get a strong handle to it. The asset is owned
+/// by the [`SharedStore`] in between, so it can't be freed before it's received.
+///
+/// Assets are received in the order they were sent.
+#[derive(SystemParam)]
+pub struct AssetHandoff<'w, T: Asset>
+{
+    store: Res<'w, SharedStore>,
+    assets: ResMut<'w, Assets<T>>,
+}
+
+impl<'w, T: Asset> AssetHandoff<'w, T>
+{
+    /// Moves an asset out of this world so it can be received by another world.
+    ///
+    /// Returns `false` if the asset isn't loaded.
+    pub fn send(&mut self, handle: &Handle<T>) -> bool
+    {
+        let Some(asset) = self.assets.remove(handle) else { return false };
+        if !self.store.contains::<PendingAssetHandoffs<T>>() {
+            self.store.insert(PendingAssetHandoffs::<T> { assets: VecDeque::default() });
+        }
+        self.store
+            .modify(|pending: &mut PendingAssetHandoffs<T>| pending.assets.push_back(asset))
+            .is_some()
+    }
+
+    /// Receives the oldest asset sent by any world.
+    ///
+    /// Returns a strong handle to the asset in this world.
+    pub fn receive(&mut self) -> Option<Handle<T>>
+    {
+        let asset = self
+            .store
+            .modify(|pending: &mut PendingAssetHandoffs<T>| pending.assets.pop_front())??;
+        Some(self.assets.add(asset))
+    }
+
+    /// Returns the number of assets waiting to be received.
+    pub fn pending(&self) -> usize
+    {
+        self.store
+            .read(|pending: &PendingAssetHandoffs<T>| pending.assets.len())
+            .unwrap_or(0)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,55 @@
+use std::any::Any;
+
+use bevy::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event delivered to the foreground world when another world sends an attention request with
+/// [`AttentionSender`].
+///
+/// Attention requests let a background world notify the foreground world (e.g. "match found") without forcing a
+/// swap.
+#[derive(Event)]
+pub struct AttentionRequest
+{
+    payload: Box<dyn Any + Send + Sync>,
+}
+
+impl AttentionRequest
+{
+    /// Gets the request's payload if it has type `T`.
+    pub fn payload<T: 'static>(&self) -> Option<&T>
+    {
+        self.payload.downcast_ref::<T>()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for sending [`AttentionRequests`](AttentionRequest) to the foreground world.
+///
+/// Requests are delivered to whichever world is in the foreground the next time the backend runs (after swap
+/// commands are applied). Requests sent by a background world will usually be delivered in the following tick.
+/// If a request is sent by the foreground world, it will be delivered back to the foreground world.
+#[derive(Resource, Clone)]
+pub struct AttentionSender(pub(crate) BoundedSender<AttentionRequest>);
+
+impl AttentionSender
+{
+    /// Sends an [`AttentionRequest`] with the given payload.
+    pub fn send<T: Any + Send + Sync>(&self, payload: T)
+    {
+        // Ignore errors.
+        let _ = self.0.send(AttentionRequest { payload: Box::new(payload) });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for receiving [`AttentionRequests`](AttentionRequest).
+///
+/// Only used in [`WorldSwapSubApp`](crate::WorldSwapSubApp).
+#[derive(Resource, Deref)]
+pub(crate) struct AttentionReceiver(pub(crate) BoundedReceiver<AttentionRequest>);
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,275 @@
+#[cfg(feature = "audio")]
+use bevy::audio::AudioPlaySet;
+use bevy::app::{FixedMainScheduleOrder, MainScheduleOrder};
+use bevy::ecs::schedule::{InternedScheduleLabel, InternedSystemSet, ScheduleLabel};
+use bevy::input::InputSystem;
+#[cfg(feature = "pbr")]
+use bevy::pbr::SimulationLightSystems;
+use bevy::prelude::*;
+#[cfg(feature = "render")]
+use bevy::render::camera::CameraUpdateSystem;
+#[cfg(feature = "render")]
+use bevy::render::view::VisibilitySystems;
+#[cfg(feature = "ui")]
+use bevy::ui::UiSystem;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs a world's [`Main`] schedule without the schedules denied by its [`BackgroundSystemPolicy`].
+pub(crate) fn run_main_with_schedule_filter(world: &mut World)
+{
+    let Some(filter) = world.get_resource::<BackgroundScheduleFilter>() else {
+        world.run_schedule(Main);
+        return;
+    };
+    let denied = filter.0.clone();
+
+    // Remove denied schedules from the schedule orders.
+    let main_order = world.get_resource_mut::<MainScheduleOrder>().map(|mut order| {
+        let labels = order.labels.clone();
+        order.labels.retain(|label| !denied.contains(label));
+        labels
+    });
+    let fixed_order = world.get_resource_mut::<FixedMainScheduleOrder>().map(|mut order| {
+        let labels = order.labels.clone();
+        order.labels.retain(|label| !denied.contains(label));
+        labels
+    });
+
+    world.run_schedule(Main);
+
+    // Restore the schedule orders.
+    if let (Some(labels), Some(mut order)) = (main_order, world.get_resource_mut::<MainScheduleOrder>()) {
+        order.labels = labels;
+    }
+    if let (Some(labels), Some(mut order)) = (fixed_order, world.get_resource_mut::<FixedMainScheduleOrder>()) {
+        order.labels = labels;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Marks cameras and visibility as changed so systems skipped in the background fully recompute them.
+#[cfg(feature = "render")]
+fn refresh_visibility(
+    mut visibilities: Query<&mut Visibility>,
+    mut projections: Query<&mut Projection>,
+    mut orthographic: Query<&mut OrthographicProjection>,
+    mut perspective: Query<&mut PerspectiveProjection>,
+)
+{
+    visibilities.iter_mut().for_each(|mut visibility| visibility.set_changed());
+    projections.iter_mut().for_each(|mut projection| projection.set_changed());
+    orthographic.iter_mut().for_each(|mut projection| projection.set_changed());
+    perspective.iter_mut().for_each(|mut projection| projection.set_changed());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Marks lights as changed so light systems skipped in the background fully recompute them.
+#[cfg(feature = "pbr")]
+fn refresh_lights(
+    mut point_lights: Query<&mut PointLight>,
+    mut spot_lights: Query<&mut SpotLight>,
+    mut directional_lights: Query<&mut DirectionalLight>,
+)
+{
+    point_lights.iter_mut().for_each(|mut light| light.set_changed());
+    spot_lights.iter_mut().for_each(|mut light| light.set_changed());
+    directional_lights.iter_mut().for_each(|mut light| light.set_changed());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Schedules that won't run while a world updates in the background.
+#[derive(Resource, Clone, Default)]
+pub(crate) struct BackgroundScheduleFilter(Vec<InternedScheduleLabel>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that controls which system sets and schedules run while a world is not in the foreground.
+///
+/// Denied system sets get an [`in_foreground`] run condition. Denied schedules are skipped by the `bevy_worldswap`
+/// backend when it updates the world in the background. Schedules can only be denied if they are run by [`Main`]
+/// (i.e. listed in [`MainScheduleOrder`] or [`FixedMainScheduleOrder`]).
+///
+/// The world is not in the foreground during [`WorldSwapApp::with_warmup_ticks`] updates or
+/// [`SwapCommand::StepBackground`] and [`SwapCommand::AdvanceBackground`] updates, so denied sets and schedules
+/// are skipped in those updates too.
+///
+/// Start from [`Self::background_safe`] to extend the defaults used by [`BackgroundSafePlugin`].
+///
+/// If visibility is refreshed (see [`Self::refresh_visibility`]), then cameras, visibility, and lights (`pbr`
+/// feature) are marked changed when the world enters the foreground. This makes the visibility and light systems
+/// do a full recomputation after being skipped in the background.
+///
+/// Don't add this and [`BackgroundSafePlugin`] to the same app.
+#[derive(Clone, Default)]
+pub struct BackgroundSystemPolicy
+{
+    denied_sets: Vec<(InternedScheduleLabel, InternedSystemSet)>,
+    denied_schedules: Vec<InternedScheduleLabel>,
+    refresh_visibility: bool,
+}
+
+impl BackgroundSystemPolicy
+{
+    /// Makes a policy that doesn't deny anything.
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// Makes a policy that denies the sets listed in [`BackgroundSafePlugin`] and refreshes visibility on return
+    /// to the foreground.
+    pub fn background_safe() -> Self
+    {
+        let policy = Self::new().deny_set(PreUpdate, InputSystem).refresh_visibility(true);
+
+        #[cfg(feature = "render")]
+        let policy = policy
+            .deny_set(PostUpdate, CameraUpdateSystem)
+            .deny_set(PostUpdate, VisibilitySystems::CalculateBounds)
+            .deny_set(PostUpdate, VisibilitySystems::UpdateFrusta)
+            .deny_set(PostUpdate, VisibilitySystems::VisibilityPropagate)
+            .deny_set(PostUpdate, VisibilitySystems::CheckVisibility);
+
+        #[cfg(feature = "audio")]
+        let policy = policy.deny_set(PostUpdate, AudioPlaySet);
+
+        #[cfg(feature = "ui")]
+        let policy = policy
+            .deny_set(PreUpdate, UiSystem::Focus)
+            .deny_set(PostUpdate, UiSystem::Layout);
+
+        #[cfg(feature = "pbr")]
+        let policy = policy
+            .deny_set(PostUpdate, SimulationLightSystems::AddClusters)
+            .deny_set(PostUpdate, SimulationLightSystems::AssignLightsToClusters)
+            .deny_set(PostUpdate, SimulationLightSystems::UpdateDirectionalLightCascades)
+            .deny_set(PostUpdate, SimulationLightSystems::UpdateLightFrusta)
+            .deny_set(PostUpdate, SimulationLightSystems::CheckLightVisibility);
+
+        policy
+    }
+
+    /// Prevents a system set in a schedule from running in the background.
+    pub fn deny_set(mut self, schedule: impl ScheduleLabel, set: impl SystemSet) -> Self
+    {
+        let entry = (schedule.intern(), set.intern());
+        if !self.denied_sets.contains(&entry) {
+            self.denied_sets.push(entry);
+        }
+        self
+    }
+
+    /// Allows a previously-denied system set to run in the background.
+    pub fn allow_set(mut self, schedule: impl ScheduleLabel, set: impl SystemSet) -> Self
+    {
+        let entry = (schedule.intern(), set.intern());
+        self.denied_sets.retain(|denied| *denied != entry);
+        self
+    }
+
+    /// Prevents a schedule from running in the background.
+    pub fn deny_schedule(mut self, schedule: impl ScheduleLabel) -> Self
+    {
+        let schedule = schedule.intern();
+        if !self.denied_schedules.contains(&schedule) {
+            self.denied_schedules.push(schedule);
+        }
+        self
+    }
+
+    /// Allows a previously-denied schedule to run in the background.
+    pub fn allow_schedule(mut self, schedule: impl ScheduleLabel) -> Self
+    {
+        let schedule = schedule.intern();
+        self.denied_schedules.retain(|denied| *denied != schedule);
+        self
+    }
+
+    /// Sets whether visibility should be fully recomputed when the world enters the foreground.
+    ///
+    /// Use this if visibility or light systems are denied. Does nothing without the `render` feature.
+    pub fn refresh_visibility(mut self, refresh: bool) -> Self
+    {
+        self.refresh_visibility = refresh;
+        self
+    }
+
+    fn apply(&self, app: &mut App)
+    {
+        for (schedule, set) in self.denied_sets.iter() {
+            app.configure_sets(*schedule, set.run_if(in_foreground));
+        }
+        if !self.denied_schedules.is_empty() {
+            app.insert_resource(BackgroundScheduleFilter(self.denied_schedules.clone()));
+        }
+
+        if self.refresh_visibility {
+            #[cfg(feature = "render")]
+            app.add_systems(
+                PostUpdate,
+                refresh_visibility
+                    .run_if(entered_foreground)
+                    .before(CameraUpdateSystem)
+                    .before(VisibilitySystems::CalculateBounds)
+                    .before(VisibilitySystems::UpdateFrusta)
+                    .before(VisibilitySystems::VisibilityPropagate),
+            );
+            #[cfg(feature = "pbr")]
+            app.add_systems(
+                PostUpdate,
+                refresh_lights
+                    .run_if(entered_foreground)
+                    .before(SimulationLightSystems::AddClusters)
+                    .before(SimulationLightSystems::UpdateLightFrusta),
+            );
+        }
+    }
+}
+
+impl Plugin for BackgroundSystemPolicy
+{
+    fn build(&self, app: &mut App)
+    {
+        self.apply(app);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that disables known Bevy system sets while a world is not in the foreground.
+///
+/// Use this in worlds with [`BackgroundTickRate::EveryTick`] (or other tick rates that update in the background)
+/// so they don't waste time on input and render-adjacent work.
+///
+/// The following sets will only run while the world is in the foreground:
+/// - [`InputSystem`]
+/// - `CameraUpdateSystem` and `VisibilitySystems` (`render` feature)
+/// - `AudioPlaySet` (`audio` feature)
+/// - `UiSystem::Focus` and `UiSystem::Layout` (`ui` feature)
+/// - `SimulationLightSystems` (`pbr` feature)
+///
+/// Cameras, visibility, and lights are fully recomputed when the world returns to the foreground.
+///
+/// Text layout is not disabled, because Bevy's text layout systems are not in public system sets. Use
+/// [`BackgroundSystemPolicy`] to deny your own sets if text layout is expensive in your app.
+///
+/// Gizmos are disabled outside the foreground by the `gizmos` feature.
+///
+/// Use [`BackgroundSystemPolicy`] for finer control.
+pub struct BackgroundSafePlugin;
+
+impl Plugin for BackgroundSafePlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        BackgroundSystemPolicy::background_safe().apply(app);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
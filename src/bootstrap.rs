@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Factory for the real world started by [`SplashBootstrapPlugin`].
+///
+/// Called with the splash world so the factory can clone its [`AssetServer`] into the new app and start loading
+/// assets.
+pub type SplashBootstrapFn = fn(&mut World) -> SplashBootstrap;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Keeps the assets loaded for a bootstrapped world alive after it enters the foreground.
+#[derive(Resource)]
+struct BootstrapHandles
+{
+    _handles: Vec<UntypedHandle>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn start_bootstrap(world: &mut World, factory: SplashBootstrapFn)
+{
+    let SplashBootstrap { mut app, handles, condition } = (factory)(world);
+    app.world.insert_resource(BootstrapHandles { _handles: handles.clone() });
+
+    let mut pending = handles
+        .into_iter()
+        .fold(PendingSwap::new(SwapCommand::Pass(app)), |pending, handle| pending.with_handle(handle));
+    if let Some(condition) = condition {
+        pending = pending.with_condition(condition);
+    }
+    world.insert_resource(pending);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The real world started by [`SplashBootstrapPlugin`], along with what it needs before it can be shown.
+pub struct SplashBootstrap
+{
+    app: WorldSwapApp,
+    handles: Vec<UntypedHandle>,
+    condition: Option<fn(&World) -> bool>,
+}
+
+impl SplashBootstrap
+{
+    /// Makes a new bootstrap for the given world.
+    ///
+    /// Use [`WorldSwapApp::with_warmup_ticks`] to run the world's startup before it is shown.
+    pub fn new(app: WorldSwapApp) -> Self
+    {
+        Self { app, handles: Vec::default(), condition: None }
+    }
+
+    /// Adds an asset that must be loaded before passing to the world.
+    ///
+    /// The handle is kept alive in the world after it enters the foreground.
+    pub fn with_handle(mut self, handle: impl Into<UntypedHandle>) -> Self
+    {
+        self.handles.push(handle.into());
+        self
+    }
+
+    /// Adds a readiness condition that is checked in the splash world (e.g. a minimum display time for the logo).
+    pub fn with_condition(mut self, condition: fn(&World) -> bool) -> Self
+    {
+        self.condition = Some(condition);
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin for a splash-screen world that passes to the real world once it is ready.
+///
+/// Add this to the initial app (alongside [`WorldSwapPlugin`]) and use it to show a logo or loading screen. In
+/// [`Startup`], the factory makes the real world, and a [`PendingSwap`] with [`SwapCommand::Pass`] is inserted
+/// into the splash world. The splash world is dropped (or passed to [`WorldSwapPlugin::swap_pass_recovery`])
+/// when the real world enters the foreground.
+pub struct SplashBootstrapPlugin
+{
+    factory: SplashBootstrapFn,
+}
+
+impl SplashBootstrapPlugin
+{
+    /// Makes a new plugin.
+    pub fn new(factory: SplashBootstrapFn) -> Self
+    {
+        Self { factory }
+    }
+}
+
+impl Plugin for SplashBootstrapPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        let factory = self.factory;
+        app.add_systems(Startup, move |world: &mut World| start_bootstrap(world, factory));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,299 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crossbeam::channel::{Receiver, Sender, TryRecvError, TrySendError};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// What happens when a message is sent to a full bounded channel. See [`ChannelBound`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OverflowPolicy
+{
+    /// Drop the oldest message in the channel to make room.
+    DropOldest,
+    /// Drop the message being sent.
+    DropNewest,
+    /// Block the sender until there is room.
+    ///
+    /// Only use this for channels whose senders run on other threads. Worlds and the backend run on the same
+    /// thread, so blocking there will deadlock the app. [`WorldSwapPlugin`] rejects this policy for its channels.
+    ///
+    /// [`WorldSwapPlugin`]: crate::WorldSwapPlugin
+    Block,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Capacity of a cross-world channel created by this crate.
+///
+/// Channels are unbounded by default, which means they can grow without limit if the receiving side stalls.
+///
+/// See [`WorldSwapPlugin::channel_bound`](crate::WorldSwapPlugin::channel_bound).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ChannelBound
+{
+    /// The channel can hold any number of messages.
+    #[default]
+    Unbounded,
+    /// The channel can hold up to `capacity` messages. Extra messages are handled by the overflow policy.
+    Bounded
+    {
+        capacity: usize,
+        overflow: OverflowPolicy,
+    },
+}
+
+impl ChannelBound
+{
+    /// Makes a channel with this bound.
+    pub(crate) fn channel<T>(self) -> (BoundedSender<T>, BoundedReceiver<T>)
+    {
+        match self {
+            Self::Unbounded => {
+                let (sender, receiver) = crossbeam::channel::unbounded();
+                (
+                    BoundedSender { inner: SenderInner::Channel(sender), overflow: None },
+                    BoundedReceiver { inner: ReceiverInner::Channel(receiver) },
+                )
+            }
+            Self::Bounded { capacity, overflow: OverflowPolicy::DropOldest } => {
+                // Crossbeam senders can't remove messages from a channel, so these channels use a shared queue.
+                let queue = Arc::new(OldestQueue {
+                    messages: Mutex::new(VecDeque::new()),
+                    capacity: capacity.max(1),
+                    senders: AtomicUsize::new(1),
+                    receivers: AtomicUsize::new(1),
+                });
+                (
+                    BoundedSender {
+                        inner: SenderInner::Queue(queue.clone()),
+                        overflow: Some(OverflowPolicy::DropOldest),
+                    },
+                    BoundedReceiver { inner: ReceiverInner::Queue(queue) },
+                )
+            }
+            Self::Bounded { capacity, overflow } => {
+                let (sender, receiver) = crossbeam::channel::bounded(capacity.max(1));
+                (
+                    BoundedSender { inner: SenderInner::Channel(sender), overflow: Some(overflow) },
+                    BoundedReceiver { inner: ReceiverInner::Channel(receiver) },
+                )
+            }
+        }
+    }
+
+    /// Returns `true` if sending on a channel with this bound can block.
+    pub(crate) fn can_block(&self) -> bool
+    {
+        matches!(self, Self::Bounded { overflow: OverflowPolicy::Block, .. })
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Messages of a channel with [`OverflowPolicy::DropOldest`].
+struct OldestQueue<T>
+{
+    messages: Mutex<VecDeque<T>>,
+    capacity: usize,
+    senders: AtomicUsize,
+    receivers: AtomicUsize,
+}
+
+impl<T> OldestQueue<T>
+{
+    fn lock(&self) -> std::sync::MutexGuard<VecDeque<T>>
+    {
+        // Messages are only pushed and popped while locked, so the queue is valid even if a holder panicked.
+        self.messages.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+enum SenderInner<T>
+{
+    Channel(Sender<T>),
+    Queue(Arc<OldestQueue<T>>),
+}
+
+enum ReceiverInner<T>
+{
+    Channel(Receiver<T>),
+    Queue(Arc<OldestQueue<T>>),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Error returned by [`BoundedSender::send`].
+#[derive(Debug)]
+pub(crate) enum SendFailure<T>
+{
+    /// The channel was full, so a message was dropped by its [`OverflowPolicy`].
+    ///
+    /// Contains the dropped message, which is either the message being sent or the oldest message in the channel.
+    Dropped(T),
+    /// All receivers were dropped.
+    Disconnected,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sender for a channel made with [`ChannelBound`].
+pub(crate) struct BoundedSender<T>
+{
+    inner: SenderInner<T>,
+    overflow: Option<OverflowPolicy>,
+}
+
+impl<T> BoundedSender<T>
+{
+    /// Sends a message, applying the channel's [`OverflowPolicy`] if it is full.
+    pub(crate) fn send(&self, message: T) -> Result<(), SendFailure<T>>
+    {
+        match &self.inner {
+            SenderInner::Channel(sender) => match self.overflow {
+                None | Some(OverflowPolicy::Block) => {
+                    sender.send(message).map_err(|_| SendFailure::Disconnected)
+                }
+                // Channels that drop the oldest message use a queue instead, so this drops the newest message.
+                Some(_) => match sender.try_send(message) {
+                    Ok(()) => Ok(()),
+                    Err(TrySendError::Full(message)) => {
+                        tracing::warn!("dropping message sent to a full channel");
+                        Err(SendFailure::Dropped(message))
+                    }
+                    Err(TrySendError::Disconnected(_)) => Err(SendFailure::Disconnected),
+                },
+            },
+            SenderInner::Queue(queue) => {
+                if queue.receivers.load(Ordering::Acquire) == 0 {
+                    return Err(SendFailure::Disconnected);
+                }
+                let mut messages = queue.lock();
+                let dropped = (messages.len() >= queue.capacity)
+                    .then(|| messages.pop_front())
+                    .flatten();
+                messages.push_back(message);
+                match dropped {
+                    Some(dropped) => {
+                        tracing::warn!("dropping oldest message in a full channel");
+                        Err(SendFailure::Dropped(dropped))
+                    }
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the channel is empty.
+    pub(crate) fn is_empty(&self) -> bool
+    {
+        match &self.inner {
+            SenderInner::Channel(sender) => sender.is_empty(),
+            SenderInner::Queue(queue) => queue.lock().is_empty(),
+        }
+    }
+
+    /// Returns `true` if both senders send to the same channel.
+    pub(crate) fn same_channel(&self, other: &Self) -> bool
+    {
+        match (&self.inner, &other.inner) {
+            (SenderInner::Channel(a), SenderInner::Channel(b)) => a.same_channel(b),
+            (SenderInner::Queue(a), SenderInner::Queue(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl<T> Clone for BoundedSender<T>
+{
+    fn clone(&self) -> Self
+    {
+        let inner = match &self.inner {
+            SenderInner::Channel(sender) => SenderInner::Channel(sender.clone()),
+            SenderInner::Queue(queue) => {
+                queue.senders.fetch_add(1, Ordering::AcqRel);
+                SenderInner::Queue(queue.clone())
+            }
+        };
+        Self { inner, overflow: self.overflow }
+    }
+}
+
+impl<T> Drop for BoundedSender<T>
+{
+    fn drop(&mut self)
+    {
+        if let SenderInner::Queue(queue) = &self.inner {
+            queue.senders.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Receiver for a channel made with [`ChannelBound`].
+pub(crate) struct BoundedReceiver<T>
+{
+    inner: ReceiverInner<T>,
+}
+
+impl<T> BoundedReceiver<T>
+{
+    /// Receives a message if one is available.
+    pub(crate) fn try_recv(&self) -> Result<T, TryRecvError>
+    {
+        match &self.inner {
+            ReceiverInner::Channel(receiver) => receiver.try_recv(),
+            ReceiverInner::Queue(queue) => match queue.lock().pop_front() {
+                Some(message) => Ok(message),
+                None if queue.senders.load(Ordering::Acquire) == 0 => Err(TryRecvError::Disconnected),
+                None => Err(TryRecvError::Empty),
+            },
+        }
+    }
+
+    /// Gets the number of messages in the channel.
+    pub(crate) fn len(&self) -> usize
+    {
+        match &self.inner {
+            ReceiverInner::Channel(receiver) => receiver.len(),
+            ReceiverInner::Queue(queue) => queue.lock().len(),
+        }
+    }
+
+    /// Returns `true` if the channel is empty.
+    pub(crate) fn is_empty(&self) -> bool
+    {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for BoundedReceiver<T>
+{
+    fn clone(&self) -> Self
+    {
+        let inner = match &self.inner {
+            ReceiverInner::Channel(receiver) => ReceiverInner::Channel(receiver.clone()),
+            ReceiverInner::Queue(queue) => {
+                queue.receivers.fetch_add(1, Ordering::AcqRel);
+                ReceiverInner::Queue(queue.clone())
+            }
+        };
+        Self { inner }
+    }
+}
+
+impl<T> Drop for BoundedReceiver<T>
+{
+    fn drop(&mut self)
+    {
+        if let ReceiverInner::Queue(queue) = &self.inner {
+            queue.receivers.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,128 @@
+use bevy::ecs::event::EventRegistry;
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn receive_channel_messages<T: Send + Sync + 'static>(
+    receiver: Res<ChannelReceiver<T>>,
+    mut messages: EventWriter<ChannelMessage<T>>,
+)
+{
+    while let Ok(message) = receiver.0.try_recv() {
+        messages.send(ChannelMessage(message));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn install_channel_end<T: Send + Sync + 'static>(
+    world: &mut World,
+    sender: BoundedSender<T>,
+    receiver: BoundedReceiver<T>,
+)
+{
+    // Only set up the receiver system once, in case the world is paired with several worlds over time.
+    let installed = world.contains_resource::<ChannelReceiver<T>>();
+    world.insert_resource(ChannelSender { sender });
+    world.insert_resource(ChannelReceiver(receiver));
+    if installed {
+        return;
+    }
+
+    if !world.contains_resource::<Events<ChannelMessage<T>>>() {
+        EventRegistry::register_event::<ChannelMessage<T>>(world);
+    }
+    world
+        .resource_mut::<Schedules>()
+        .add_systems(First, receive_channel_messages::<T>);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event emitted in a world when a message is received from its paired world.
+///
+/// See [`WorldSwapChannels`].
+#[derive(Event, Debug, Clone)]
+pub struct ChannelMessage<T>(pub T);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for sending messages to a world's paired world.
+///
+/// Messages are received as [`ChannelMessage<T>`] events at the start of the paired world's next update. See
+/// [`WorldSwapChannels`].
+#[derive(Resource)]
+pub struct ChannelSender<T>
+{
+    sender: BoundedSender<T>,
+}
+
+impl<T> ChannelSender<T>
+{
+    /// Sends a message to the paired world.
+    pub fn send(&self, message: T)
+    {
+        // Ignore errors.
+        let _ = self.sender.send(message);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for receiving messages from a world's paired world.
+#[derive(Resource)]
+struct ChannelReceiver<T>(BoundedReceiver<T>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Two-way channel between a parent world and a child world.
+///
+/// Use [`Self::install`] before sending [`SwapCommand::Fork`](crate::SwapCommand::Fork) or
+/// [`SwapCommand::Pass`](crate::SwapCommand::Pass) with the child. Each world gets a [`ChannelSender<T>`] for
+/// sending messages to the other world, and receives messages as [`ChannelMessage<T>`] events at the start of each
+/// update (in the foreground or background).
+///
+/// Installing a new pair replaces the channel ends a world already has for `T`.
+pub struct WorldSwapChannels<T>
+{
+    to_child: (BoundedSender<T>, BoundedReceiver<T>),
+    to_parent: (BoundedSender<T>, BoundedReceiver<T>),
+}
+
+impl<T: Send + Sync + 'static> WorldSwapChannels<T>
+{
+    /// Makes a new pair of channels.
+    pub fn new() -> Self
+    {
+        Self::with_bound(ChannelBound::Unbounded)
+    }
+
+    /// Makes a new pair of channels with the given [`ChannelBound`].
+    pub fn with_bound(bound: ChannelBound) -> Self
+    {
+        Self { to_child: bound.channel(), to_parent: bound.channel() }
+    }
+
+    /// Installs one end of the channels in each world.
+    ///
+    /// The child world is usually the world in a [`WorldSwapApp`](crate::WorldSwapApp) that is about to be forked.
+    pub fn install(self, parent: &mut World, child: &mut World)
+    {
+        let (to_child_sender, to_child_receiver) = self.to_child;
+        let (to_parent_sender, to_parent_receiver) = self.to_parent;
+        install_channel_end(parent, to_child_sender, to_parent_receiver);
+        install_channel_end(child, to_parent_sender, to_child_receiver);
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for WorldSwapChannels<T>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
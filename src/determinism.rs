@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource with the seed of a world's [`WorldRng`].
+///
+/// Set with [`WorldSwapApp::with_seed`] or [`DeterminismPlugin`]. Seeds of live worlds are recorded in the
+/// [`WorldRegistry`].
+#[derive(Resource, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct WorldSeed(pub u64);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Seeded random number generator resource for reproducible worlds.
+///
+/// Inserted into worlds that have a [`WorldSeed`]. The generator is a small non-cryptographic PRNG (SplitMix64),
+/// so the same seed produces the same sequence on every platform.
+#[derive(Resource, Debug, Clone)]
+pub struct WorldRng
+{
+    state: u64,
+}
+
+impl WorldRng
+{
+    /// Makes a new generator from a seed.
+    pub fn new(seed: u64) -> Self
+    {
+        Self { state: seed }
+    }
+
+    /// Gets the next random `u64`.
+    pub fn next_u64(&mut self) -> u64
+    {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Gets the next random `f64` in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64
+    {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Gets the next random `f32` in `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32
+    {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Installs a [`WorldRng`] in a world entering the foreground for the first time.
+///
+/// Worlds without a [`WorldSeed`] get a seed from the outgoing world's [`WorldRng`], so a tree of worlds forked
+/// from a seeded world is reproducible. Worlds that already have a [`WorldRng`] are not changed.
+///
+/// Called before warm-up ticks so warm-up is deterministic too.
+pub(crate) fn install_world_rng(outgoing: &mut World, incoming: &mut World)
+{
+    if incoming.contains_resource::<WorldRng>() {
+        return;
+    }
+    let seed = match incoming.get_resource::<WorldSeed>() {
+        Some(seed) => seed.0,
+        None => {
+            let Some(mut rng) = outgoing.get_resource_mut::<WorldRng>() else { return };
+            let seed = rng.next_u64();
+            incoming.insert_resource(WorldSeed(seed));
+            seed
+        }
+    };
+    incoming.insert_resource(WorldRng::new(seed));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that seeds the initial world for reproducible simulations.
+///
+/// Inserts a [`WorldSeed`] and [`WorldRng`] into the app. Worlds that are swapped in with [`SwapCommand::Pass`] or
+/// [`SwapCommand::Fork`] get their own [`WorldRng`], seeded with [`WorldSwapApp::with_seed`] or else with the next
+/// value of the outgoing world's [`WorldRng`].
+///
+/// Add this to the initial app. Use [`WorldSwapApp::with_seed`] to seed individual worlds.
+pub struct DeterminismPlugin
+{
+    /// The initial world's seed.
+    pub seed: u64,
+}
+
+impl Plugin for DeterminismPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.insert_resource(WorldSeed(self.seed))
+            .insert_resource(WorldRng::new(self.seed));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
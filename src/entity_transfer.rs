@@ -0,0 +1,153 @@
+use bevy::ecs::entity::{EntityHashMap, EntityMapper, MapEntities};
+use bevy::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Maps entities from the source world to entities in the destination world.
+///
+/// Entities that weren't transferred are mapped to [`Entity::PLACEHOLDER`].
+struct TransferMapper<'a>
+{
+    map: &'a EntityHashMap<Entity>,
+}
+
+impl EntityMapper for TransferMapper<'_>
+{
+    fn map_entity(&mut self, entity: Entity) -> Entity
+    {
+        self.map.get(&entity).copied().unwrap_or(Entity::PLACEHOLDER)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn copy_component<C: Component + Clone>(src: &World, dst: &mut World, map: &EntityHashMap<Entity>)
+{
+    for (src_entity, dst_entity) in map.iter() {
+        let Some(component) = src.get::<C>(*src_entity) else { continue };
+        dst.entity_mut(*dst_entity).insert(component.clone());
+    }
+}
+
+fn copy_mapped_component<C: Component + Clone + MapEntities>(
+    src: &World,
+    dst: &mut World,
+    map: &EntityHashMap<Entity>,
+)
+{
+    for (src_entity, dst_entity) in map.iter() {
+        let Some(component) = src.get::<C>(*src_entity) else { continue };
+        let mut component = component.clone();
+        component.map_entities(&mut TransferMapper { map });
+        dst.entity_mut(*dst_entity).insert(component);
+    }
+}
+
+fn copy_resource<R: Resource + Clone>(src: &World, dst: &mut World, _map: &EntityHashMap<Entity>)
+{
+    let Some(resource) = src.get_resource::<R>() else { return };
+    dst.insert_resource(resource.clone());
+}
+
+fn copy_mapped_resource<R: Resource + Clone + MapEntities>(
+    src: &World,
+    dst: &mut World,
+    map: &EntityHashMap<Entity>,
+)
+{
+    let Some(resource) = src.get_resource::<R>() else { return };
+    let mut resource = resource.clone();
+    resource.map_entities(&mut TransferMapper { map });
+    dst.insert_resource(resource);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+type CopyFn = fn(&World, &mut World, &EntityHashMap<Entity>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Helper for copying entities and resources between worlds in [`SwapRecoveryFn`](crate::SwapRecoveryFn)
+/// callbacks.
+///
+/// Entity ids are only valid in the world that spawned them. Components and resources registered as 'mapped' have
+/// their entity references remapped with [`MapEntities`] so they point to the copied entities. References to
+/// entities that weren't copied are set to [`Entity::PLACEHOLDER`].
+///
+/// Only registered components and resources are copied.
+#[derive(Default, Clone)]
+pub struct EntityTransfer
+{
+    components: Vec<CopyFn>,
+    resources: Vec<CopyFn>,
+}
+
+impl EntityTransfer
+{
+    /// Makes an empty transfer.
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// Registers a component that will be cloned as-is.
+    pub fn component<C: Component + Clone>(mut self) -> Self
+    {
+        self.components.push(copy_component::<C>);
+        self
+    }
+
+    /// Registers a component whose entity references will be remapped.
+    pub fn mapped_component<C: Component + Clone + MapEntities>(mut self) -> Self
+    {
+        self.components.push(copy_mapped_component::<C>);
+        self
+    }
+
+    /// Registers a resource that will be cloned as-is.
+    pub fn resource<R: Resource + Clone>(mut self) -> Self
+    {
+        self.resources.push(copy_resource::<R>);
+        self
+    }
+
+    /// Registers a resource whose entity references will be remapped.
+    pub fn mapped_resource<R: Resource + Clone + MapEntities>(mut self) -> Self
+    {
+        self.resources.push(copy_mapped_resource::<R>);
+        self
+    }
+
+    /// Copies entities from `src` into new entities in `dst`, then copies registered resources.
+    ///
+    /// Entities that don't exist in `src` are ignored.
+    ///
+    /// Returns a map from `src` entities to the new `dst` entities.
+    pub fn copy(
+        &self,
+        src: &World,
+        entities: impl IntoIterator<Item = Entity>,
+        dst: &mut World,
+    ) -> EntityHashMap<Entity>
+    {
+        // Spawn all entities first so references between them can be mapped.
+        let mut map = EntityHashMap::default();
+        for entity in entities {
+            if src.get_entity(entity).is_none() || map.contains_key(&entity) {
+                continue;
+            }
+            map.insert(entity, dst.spawn_empty().id());
+        }
+
+        for copy_fn in self.components.iter() {
+            (copy_fn)(src, dst, &map);
+        }
+        for copy_fn in self.resources.iter() {
+            (copy_fn)(src, dst, &map);
+        }
+
+        map
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,234 @@
+use std::fmt::{Display, Formatter};
+
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Misconfigurations detected while setting up [`WorldSwapPlugin`].
+///
+/// [`WorldSwapPlugin`] will panic with these errors when added with [`App::add_plugins`]. Use
+/// [`WorldSwapPlugin::try_add`] to detect build-time errors without panicking.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WorldSwapPluginError
+{
+    /// The app's [`App::main_schedule_label`] is not [`Main`].
+    MainScheduleNotMain,
+    /// The app already has a `WorldSwapPlugin`.
+    DuplicatePlugin,
+    /// The plugin was added to a child app.
+    ChildApp,
+    /// A `RenderApp` was added to the app after the plugin was built.
+    AddedBeforeRenderPlugin,
+    /// The app's render subapps were not consolidated when the plugin was cleaned up.
+    RenderSubAppsNotConsolidated,
+    /// The app's `RenderApp` is missing its `RenderInstance`.
+    MissingRenderInstance,
+    /// [`WorldSwapPlugin::channel_bound`] uses [`OverflowPolicy::Block`], which would deadlock the app.
+    BlockingChannelBound,
+}
+
+impl Display for WorldSwapPluginError
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self {
+            Self::MainScheduleNotMain => f.write_str(
+                "WorldSwapPlugin requires the app's main_schedule_label to be Main; child apps are updated by \
+                running Main, so all worlds must use the same main schedule",
+            ),
+            Self::DuplicatePlugin => f.write_str("WorldSwapPlugin was added to an app that already has it"),
+            Self::ChildApp => f.write_str(
+                "WorldSwapPlugin was added to a child app; only the initial app should use WorldSwapPlugin, \
+                child apps should use ChildDefaultPlugins (windowed) or MinimalPlugins (headless)",
+            ),
+            Self::AddedBeforeRenderPlugin => f.write_str(
+                "WorldSwapPlugin was added before RenderPlugin; add WorldSwapPlugin **after** DefaultPlugins",
+            ),
+            Self::RenderSubAppsNotConsolidated => f.write_str(
+                "found both RenderApp and RenderExtractApp during cleanup; WorldSwapPlugin must be added after \
+                DefaultPlugins so pipelined rendering can consolidate them first",
+            ),
+            Self::MissingRenderInstance => f.write_str(
+                "RenderApp is missing RenderInstance; WorldSwapPlugin must be added **after** RenderPlugin, and \
+                RenderPlugin must create its renderer during Plugin::finish",
+            ),
+            Self::BlockingChannelBound => f.write_str(
+                "WorldSwapPlugin::channel_bound uses OverflowPolicy::Block; worlds send swap commands on the \
+                app's thread, so a full channel would deadlock the app",
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WorldSwapPluginError {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Errors that can occur when constructing a [`WorldSwapApp`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WorldSwapAppError
+{
+    /// The app's [`App::main_schedule_label`] is not [`Main`].
+    MainScheduleNotMain,
+    /// The app contains a [`WorldSwapPlugin`].
+    HasWorldSwapPlugin,
+}
+
+impl Display for WorldSwapAppError
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self {
+            Self::MainScheduleNotMain => f.write_str("app's main_schedule_label is not Main"),
+            Self::HasWorldSwapPlugin => f.write_str(
+                "app contains WorldSwapPlugin; only the initial app should use WorldSwapPlugin, child apps \
+                should use ChildDefaultPlugins (windowed) or MinimalPlugins (headless)",
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WorldSwapAppError {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reasons a [`SwapCommand`] can fail.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SwapErrorReason
+{
+    /// The foreground world uses windows but the incoming world doesn't have window plumbing.
+    ///
+    /// Windowed child apps should be made with [`ChildDefaultPlugins`].
+    MissingWindowPlumbing,
+    /// The foreground world has OS windows without matching window entities, so they can't be moved to the
+    /// incoming world.
+    WindowMismatch,
+    /// The incoming world created windows while it wasn't in the foreground.
+    ///
+    /// See [`WorldSwapPlugin::pre_foreground_windows`].
+    WindowsCreatedOutsideForeground,
+    /// The incoming world is linked to a different `bevy_worldswap` backend.
+    ForeignSwapCommandSender,
+    /// The incoming world's renderer isn't linked to this app's [`RenderWorkerTarget`].
+    ///
+    /// Child apps that render should be made with [`ChildDefaultPlugins`].
+    RenderWorkerTargetMismatch,
+    /// A [`SwapCommand::Fork`] was sent while there is already a world in the background.
+    BackgroundOccupied,
+    /// A [`SwapCommand::Swap`], [`SwapCommand::Join`], [`SwapCommand::StepBackground`], or
+    /// [`SwapCommand::AdvanceBackground`] was sent while there is no world in the background. Also returned for
+    /// [`SwapCommandSender::run_in_background`] commands when there is no world in the background.
+    NoBackgroundWorld,
+    /// Another command sent in the same tick was applied instead.
+    ///
+    /// See [`CommandArbitration`] and [`DiscardPolicy`].
+    Superseded,
+    /// The swap command channel was full, so the command was dropped by its [`OverflowPolicy`].
+    ///
+    /// See [`WorldSwapPlugin::channel_bound`].
+    ChannelFull,
+}
+
+impl Display for SwapErrorReason
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self {
+            Self::MissingWindowPlumbing => {
+                f.write_str("the foreground world has windows but the incoming world is missing WinitWindows")
+            }
+            Self::WindowMismatch => {
+                f.write_str("the foreground world has OS windows without matching window entities")
+            }
+            Self::WindowsCreatedOutsideForeground => {
+                f.write_str("the incoming world created windows while not in the foreground")
+            }
+            Self::ForeignSwapCommandSender => {
+                f.write_str("the incoming world's SwapCommandSender belongs to a different app")
+            }
+            Self::RenderWorkerTargetMismatch => {
+                f.write_str("the incoming world's renderer is not linked to this app's RenderWorkerTarget")
+            }
+            Self::BackgroundOccupied => f.write_str("there is already a world in the background"),
+            Self::NoBackgroundWorld => f.write_str("there is no world in the background"),
+            Self::Superseded => f.write_str("another swap command sent in the same tick was applied instead"),
+            Self::ChannelFull => f.write_str("the swap command channel was full and the command was dropped"),
+        }
+    }
+}
+
+impl std::error::Error for SwapErrorReason {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Errors returned by [`ValidatedSwapCommands`] for commands that would fail.
+///
+/// The command is not sent. Use [`Self::into_command`] to get the command back (e.g. to re-use its
+/// [`WorldSwapApp`]).
+pub enum SwapSendError
+{
+    /// The sending world is [`Suspended`](WorldSwapStatus::Suspended) (e.g. while running warm-up ticks).
+    Suspended(SwapCommand),
+    /// The backend would fail the command with the given reason.
+    Invalid(SwapCommand, SwapErrorReason),
+}
+
+impl SwapSendError
+{
+    /// Gets the command that was not sent.
+    pub fn into_command(self) -> SwapCommand
+    {
+        match self {
+            Self::Suspended(command) | Self::Invalid(command, _) => command,
+        }
+    }
+}
+
+impl std::fmt::Debug for SwapSendError
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self {
+            Self::Suspended(command) => f.debug_tuple("Suspended").field(&command.kind()).finish(),
+            Self::Invalid(command, reason) => {
+                f.debug_tuple("Invalid").field(&command.kind()).field(reason).finish()
+            }
+        }
+    }
+}
+
+impl Display for SwapSendError
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self {
+            Self::Suspended(command) => write!(f, "{:?} command sent from a suspended world", command.kind()),
+            Self::Invalid(command, reason) => write!(f, "invalid {:?} command: {reason}", command.kind()),
+        }
+    }
+}
+
+impl std::error::Error for SwapSendError {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event sent when a [`SwapCommand`] can't be applied.
+///
+/// The event is sent to the world that sent the command if it is in the foreground or background, otherwise it is
+/// sent to the foreground world.
+///
+/// The command is discarded. If the command contained a [`WorldSwapApp`], then that app is dropped.
+#[derive(Event, Debug, Clone)]
+pub struct SwapError
+{
+    /// The id of the command that failed.
+    pub id: SwapCommandId,
+    /// The kind of command that failed.
+    pub command: SwapCommandKind,
+    /// Why the command failed.
+    pub reason: SwapErrorReason,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,112 @@
+use bevy::ecs::storage::SparseSetIndex;
+use bevy::ecs::world::WorldId;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returned by FFI functions in place of an id when the handle is null or the id doesn't exist.
+pub const WORLDSWAP_FFI_NONE: u64 = u64::MAX;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn world_to_ffi(world: WorldId) -> u64
+{
+    world.sparse_set_index() as u64
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Opaque FFI wrapper around a [`WorldSwapRemoteHandle`].
+///
+/// Make one with [`WorldSwapFfiHandle::into_raw`] and pass the pointer to a host application or scripting runtime,
+/// which can drive swaps with the `worldswap_*` functions. World ids returned by those functions can be compared
+/// with each other, but don't have meaning outside this app.
+///
+/// Requires the `ffi` feature.
+pub struct WorldSwapFfiHandle(WorldSwapRemoteHandle);
+
+impl WorldSwapFfiHandle
+{
+    /// Moves a handle to the heap and returns an owning pointer.
+    ///
+    /// The pointer must be released with [`worldswap_handle_free`].
+    pub fn into_raw(handle: WorldSwapRemoteHandle) -> *mut Self
+    {
+        Box::into_raw(Box::new(Self(handle)))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Clones an FFI handle. Returns null if `handle` is null.
+///
+/// # Safety
+/// `handle` must be null or a live pointer from [`WorldSwapFfiHandle::into_raw`] or this function.
+#[no_mangle]
+pub unsafe extern "C" fn worldswap_handle_clone(handle: *const WorldSwapFfiHandle) -> *mut WorldSwapFfiHandle
+{
+    let Some(handle) = handle.as_ref() else { return std::ptr::null_mut() };
+    WorldSwapFfiHandle::into_raw(handle.0.clone())
+}
+
+/// Releases an FFI handle. Does nothing if `handle` is null.
+///
+/// # Safety
+/// `handle` must be null or a live pointer from [`WorldSwapFfiHandle::into_raw`] or [`worldswap_handle_clone`].
+/// The pointer can't be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn worldswap_handle_free(handle: *mut WorldSwapFfiHandle)
+{
+    if handle.is_null() {
+        return;
+    }
+    drop(Box::from_raw(handle));
+}
+
+/// Sends [`SwapCommand::Swap`]. Returns the command id, or [`WORLDSWAP_FFI_NONE`] if `handle` is null.
+///
+/// # Safety
+/// See [`worldswap_handle_clone`].
+#[no_mangle]
+pub unsafe extern "C" fn worldswap_send_swap(handle: *const WorldSwapFfiHandle) -> u64
+{
+    let Some(handle) = handle.as_ref() else { return WORLDSWAP_FFI_NONE };
+    handle.0.send(SwapCommand::Swap).0
+}
+
+/// Sends [`SwapCommand::Join`]. Returns the command id, or [`WORLDSWAP_FFI_NONE`] if `handle` is null.
+///
+/// # Safety
+/// See [`worldswap_handle_clone`].
+#[no_mangle]
+pub unsafe extern "C" fn worldswap_send_join(handle: *const WorldSwapFfiHandle) -> u64
+{
+    let Some(handle) = handle.as_ref() else { return WORLDSWAP_FFI_NONE };
+    handle.0.send(SwapCommand::Join).0
+}
+
+/// Gets the id of the foreground world. Returns [`WORLDSWAP_FFI_NONE`] if `handle` is null.
+///
+/// # Safety
+/// See [`worldswap_handle_clone`].
+#[no_mangle]
+pub unsafe extern "C" fn worldswap_foreground_world(handle: *const WorldSwapFfiHandle) -> u64
+{
+    let Some(handle) = handle.as_ref() else { return WORLDSWAP_FFI_NONE };
+    world_to_ffi(handle.0.registry().foreground())
+}
+
+/// Gets the id of the background world. Returns [`WORLDSWAP_FFI_NONE`] if `handle` is null or there is no
+/// background world.
+///
+/// # Safety
+/// See [`worldswap_handle_clone`].
+#[no_mangle]
+pub unsafe extern "C" fn worldswap_background_world(handle: *const WorldSwapFfiHandle) -> u64
+{
+    let Some(handle) = handle.as_ref() else { return WORLDSWAP_FFI_NONE };
+    handle.0.registry().background().map(world_to_ffi).unwrap_or(WORLDSWAP_FFI_NONE)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use bevy::ecs::storage::SparseSetIndex;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::window::PrimaryWindow;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Configures periodic screenshots of the foreground world for [`WorldSwapPlugin::frame_capture`].
+///
+/// Every `interval` ticks, the foreground world's primary window is saved to a PNG in `directory`. The first frame
+/// rendered after each swap is always captured, so screenshot bots and CI runs can see every world that entered
+/// the foreground.
+///
+/// Files are named `<tick>-<world>.png`, where `<world>` is the world's [`WorldLabel`] or its index.
+///
+/// Captures use Bevy's screenshot support, so the app needs a window (e.g. a virtual display in CI). Worlds
+/// without a primary window are not captured.
+#[derive(Debug, Clone)]
+pub struct FrameCapture
+{
+    /// Number of ticks between captures.
+    pub interval: u32,
+    /// Directory where screenshots are written.
+    pub directory: PathBuf,
+}
+
+impl FrameCapture
+{
+    /// Makes a new config that captures every `interval` ticks into `directory`.
+    pub fn new(interval: u32, directory: impl Into<PathBuf>) -> Self
+    {
+        Self { interval: interval.max(1), directory: directory.into() }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks frame captures in the worldswap subapp.
+#[derive(Resource, Default)]
+pub(crate) struct FrameCaptureState
+{
+    tick: u64,
+    /// Set when worlds are swapped so the new world's first frame is captured.
+    capture_next: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Requests a screenshot of the foreground world if [`WorldSwapPlugin::frame_capture`] is set.
+///
+/// Must be called before the foreground world is extracted for rendering.
+pub(crate) fn capture_frame(subapp_world: &mut World, main_world: &mut World, swapped: bool)
+{
+    let Some(config) = subapp_world.resource::<WorldSwapPlugin>().frame_capture.clone() else { return };
+
+    // Worlds aren't rendered on ticks where they are swapped, so capture the next frame instead.
+    let mut state = subapp_world.resource_mut::<FrameCaptureState>();
+    if swapped {
+        state.capture_next = true;
+        return;
+    }
+    let tick = state.tick;
+    state.tick += 1;
+    if !state.capture_next && tick % u64::from(config.interval.max(1)) != 0 {
+        return;
+    }
+    state.capture_next = false;
+
+    // Request a screenshot of the primary window.
+    let Ok(window) = main_world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .get_single(main_world)
+    else {
+        return;
+    };
+    let name = match main_world.get_resource::<WorldLabel>() {
+        Some(label) => label.to_string(),
+        None => format!("world{}", main_world.id().sparse_set_index()),
+    };
+    let path = config.directory.join(format!("{tick:08}-{name}.png"));
+    let Some(mut screenshots) = main_world.get_resource_mut::<ScreenshotManager>() else { return };
+    if let Err(err) = screenshots.save_screenshot_to_disk(window, &path) {
+        tracing::warn!("failed capturing frame to {path:?}: {err}");
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,52 @@
+use std::any::TypeId;
+
+use bevy::gizmos::config::GizmoConfigStore;
+use bevy::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gizmo config groups that were disabled by the backend when the world left the foreground.
+#[derive(Resource, Default)]
+struct GizmosDisabledByBackend(Vec<TypeId>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Disables all enabled gizmo config groups so gizmos drawn outside the foreground aren't collected.
+///
+/// Otherwise gizmos queued by a world ticking in the background would be rendered in a burst when it returns.
+pub(crate) fn disable_gizmos(world: &mut World)
+{
+    if world.contains_resource::<GizmosDisabledByBackend>() {
+        return;
+    }
+    let Some(mut store) = world.get_resource_mut::<GizmoConfigStore>() else { return };
+
+    let mut disabled = Vec::default();
+    for (type_id, config, _) in store.iter_mut() {
+        if !config.enabled {
+            continue;
+        }
+        config.enabled = false;
+        disabled.push(*type_id);
+    }
+
+    world.insert_resource(GizmosDisabledByBackend(disabled));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Re-enables gizmo config groups disabled by [`disable_gizmos`].
+pub(crate) fn enable_gizmos(world: &mut World)
+{
+    let Some(disabled) = world.remove_resource::<GizmosDisabledByBackend>() else { return };
+    let Some(mut store) = world.get_resource_mut::<GizmoConfigStore>() else { return };
+
+    for (type_id, config, _) in store.iter_mut() {
+        if !disabled.0.contains(type_id) {
+            continue;
+        }
+        config.enabled = true;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stored in the [`SharedStore`] while background updates are paused with [`SwapHotkeys::toggle_background`].
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct BackgroundUpdatesPaused;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returns `true` if background updates were paused with [`SwapHotkeys::toggle_background`].
+pub(crate) fn background_updates_paused(subapp_world: &World) -> bool
+{
+    subapp_world
+        .get_resource::<SharedStore>()
+        .is_some_and(|store| store.contains::<BackgroundUpdatesPaused>())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn handle_swap_hotkeys(
+    keys: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<SwapHotkeys>,
+    sender: Res<SwapCommandSender>,
+    store: Res<SharedStore>,
+)
+{
+    let pressed = |key: Option<KeyCode>| key.is_some_and(|key| keys.just_pressed(key));
+
+    if pressed(hotkeys.swap) {
+        tracing::info!("swap hotkey pressed");
+        sender.send(SwapCommand::Swap);
+    }
+    if pressed(hotkeys.join) {
+        tracing::info!("join hotkey pressed");
+        sender.send(SwapCommand::Join);
+    }
+    if pressed(hotkeys.toggle_background) {
+        if store.remove::<BackgroundUpdatesPaused>().is_some() {
+            tracing::info!("background updates resumed by hotkey");
+        } else {
+            store.insert(BackgroundUpdatesPaused);
+            tracing::info!("background updates paused by hotkey");
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource with the key bindings of [`SwapHotkeysPlugin`].
+#[derive(Resource, Debug, Copy, Clone)]
+pub struct SwapHotkeys
+{
+    /// Sends [`SwapCommand::Swap`].
+    ///
+    /// `F6` by default.
+    pub swap: Option<KeyCode>,
+    /// Sends [`SwapCommand::Join`].
+    ///
+    /// `F7` by default.
+    pub join: Option<KeyCode>,
+    /// Pauses or resumes background updates, as if the background world's tick rate was
+    /// [`BackgroundTickRate::Never`] without freezing time.
+    ///
+    /// `F8` by default.
+    pub toggle_background: Option<KeyCode>,
+}
+
+impl Default for SwapHotkeys
+{
+    fn default() -> Self
+    {
+        Self {
+            swap: Some(KeyCode::F6),
+            join: Some(KeyCode::F7),
+            toggle_background: Some(KeyCode::F8),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Developer plugin that binds keys to swap commands, for quickly flipping between worlds while iterating.
+///
+/// Keys are only read in the foreground world, so add this plugin to every world you want to control (e.g. with
+/// the same plugin list for the menu and game worlds). See [`SwapHotkeys`].
+///
+/// Requires the `hotkeys` feature.
+#[derive(Default)]
+pub struct SwapHotkeysPlugin
+{
+    pub hotkeys: SwapHotkeys,
+}
+
+impl Plugin for SwapHotkeysPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.insert_resource(self.hotkeys).add_systems(
+            Update,
+            handle_swap_hotkeys
+                .run_if(resource_exists::<ButtonInput<KeyCode>>)
+                .run_if(resource_exists::<SwapCommandSender>)
+                .run_if(resource_exists::<SharedStore>)
+                .run_if(in_foreground),
+        );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
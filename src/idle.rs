@@ -0,0 +1,189 @@
+use std::any::TypeId;
+
+use bevy::ecs::component::Tick;
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Checks registered in a world that report pending cross-world messages for it.
+#[derive(Resource, Default)]
+struct IdleWakeChecks(Vec<fn(&World) -> bool>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Counts tracked components that were added or changed since `since`, plus the change in entity count.
+///
+/// Entities are counted by net change, so spawns and despawns in the same update cancel out.
+fn count_changes(world: &World, tracked: &[TypeId], since: Tick, prev_entities: u32) -> usize
+{
+    let this_run = world.read_change_tick();
+    let mut changed = 0;
+    for component_id in tracked.iter().filter_map(|type_id| world.components().get_id(*type_id)) {
+        // Only visit entities that have the component.
+        for archetype in world.archetypes().iter().filter(|archetype| archetype.contains(component_id)) {
+            changed += archetype
+                .entities()
+                .iter()
+                .filter_map(|archetype_entity| world.get_entity(archetype_entity.id()))
+                .filter(|entity| {
+                    entity
+                        .get_change_ticks_by_id(component_id)
+                        .is_some_and(|ticks| ticks.is_changed(since, this_run))
+                })
+                .count();
+        }
+    }
+    changed + world.entities().len().abs_diff(prev_entities) as usize
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returns `true` if the world has cross-world messages waiting.
+fn has_messages(world: &World, store_version: u64) -> bool
+{
+    if world
+        .get_resource::<SharedStore>()
+        .is_some_and(|store| store.version() != store_version)
+    {
+        return true;
+    }
+    let Some(checks) = world.get_resource::<IdleWakeChecks>() else { return false };
+    checks.0.iter().any(|check| (check)(world))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Registers a check that wakes a parked world when it has pending cross-world messages.
+pub(crate) fn register_idle_wake_check(app: &mut App, check: fn(&World) -> bool)
+{
+    app.world_mut()
+        .get_resource_or_insert_with(IdleWakeChecks::default)
+        .0
+        .push(check);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returns `true` if a background world is parked and should not update this tick.
+///
+/// Parked worlds are woken up if they have cross-world messages.
+pub(crate) fn is_idle_parked(world: &mut World) -> bool
+{
+    let Some(idle) = world.get_resource::<IdleParking>() else { return false };
+    if !idle.parked {
+        return false;
+    }
+    if !has_messages(world, idle.store_version) {
+        return true;
+    }
+
+    tracing::debug!("waking {:?} from idle parking", WorldName::of(world));
+    world.resource_mut::<IdleParking>().reset();
+    false
+}
+
+/// Starts tracking changes in a background world's update. Returns `None` if the world doesn't use
+/// [`IdleParking`].
+pub(crate) fn begin_idle_tracking(world: &World) -> Option<(Tick, u32)>
+{
+    if !world.contains_resource::<IdleParking>() {
+        return None;
+    }
+    Some((world.read_change_tick(), world.entities().len()))
+}
+
+/// Finishes tracking changes in a background world's update, and parks the world if it has been idle for long
+/// enough.
+pub(crate) fn end_idle_tracking(world: &mut World, (since, prev_entities): (Tick, u32))
+{
+    let Some(idle) = world.get_resource::<IdleParking>() else { return };
+    let changes = count_changes(world, &idle.tracked, since, prev_entities);
+    let store_version = world.get_resource::<SharedStore>().map(|store| store.version()).unwrap_or_default();
+    let Some(mut idle) = world.get_resource_mut::<IdleParking>() else { return };
+
+    idle.store_version = store_version;
+    if changes > idle.change_threshold {
+        idle.idle_updates = 0;
+        return;
+    }
+    idle.idle_updates += 1;
+    if idle.parked || idle.idle_updates < idle.idle_ticks {
+        return;
+    }
+    idle.parked = true;
+    tracing::debug!("parking idle {:?}", WorldName::of(world));
+}
+
+/// Resets idle tracking for a world that is entering the foreground.
+pub(crate) fn reset_idle_parking(world: &mut World)
+{
+    let Some(mut idle) = world.get_resource_mut::<IdleParking>() else { return };
+    idle.reset();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that lets the backend park this world while it is idle in the background.
+///
+/// An update is idle if it added or changed at most [`Self::change_threshold`] tracked components (spawned and
+/// despawned entities are also counted, but only by the net change in entity count, so an update that spawns and
+/// despawns the same number of entities counts as zero). Only components registered with [`Self::track`] are
+/// checked, so tracking costs scale with the number of entities that have them. Events and resources are not
+/// considered. After [`Self::idle_ticks`] idle updates in a row, the world stops updating as if its tick rate was
+/// [`BackgroundTickRate::Never`] with `freeze_time: false`.
+///
+/// A parked world resumes its normal tick rate when it receives a cross-world message: a [`SharedStore`] change,
+/// or an [`RpcBridge`] request. Idle tracking is reset when the world enters the foreground.
+#[derive(Resource, Debug, Clone)]
+pub struct IdleParking
+{
+    /// Number of idle updates in a row before the world is parked.
+    pub idle_ticks: u32,
+    /// Updates that add or change at most this many components are idle.
+    pub change_threshold: usize,
+    tracked: Vec<TypeId>,
+    idle_updates: u32,
+    parked: bool,
+    store_version: u64,
+}
+
+impl IdleParking
+{
+    /// Makes a new idle parking config.
+    pub fn new(idle_ticks: u32, change_threshold: usize) -> Self
+    {
+        Self {
+            idle_ticks,
+            change_threshold,
+            tracked: Vec::new(),
+            idle_updates: 0,
+            parked: false,
+            store_version: 0,
+        }
+    }
+
+    /// Counts changes to component `C` when checking if an update is idle.
+    ///
+    /// If no components are tracked, then only spawned and despawned entities are counted.
+    pub fn track<C: Component>(mut self) -> Self
+    {
+        self.tracked.push(TypeId::of::<C>());
+        self
+    }
+
+    /// Returns `true` if the world is parked.
+    pub fn is_parked(&self) -> bool
+    {
+        self.parked
+    }
+
+    fn reset(&mut self)
+    {
+        self.idle_updates = 0;
+        self.parked = false;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,318 @@
+use std::time::Duration;
+
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::mouse::{MouseButtonInput, MouseMotion, MouseWheel};
+use bevy::input::touch::TouchInput;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::window::{CursorEntered, CursorLeft};
+use bevy::winit::{WinitEvent, WinitWindows};
+use winit::window::WindowId;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the input event contained in a winit event.
+fn input_from_winit_event(event: &WinitEvent) -> Option<(Option<Entity>, RecordedInputEvent)>
+{
+    let recorded = match event.clone() {
+        WinitEvent::KeyboardInput(event) => (Some(event.window), RecordedInputEvent::Keyboard(event)),
+        WinitEvent::MouseButtonInput(event) => (Some(event.window), RecordedInputEvent::MouseButton(event)),
+        WinitEvent::MouseMotion(event) => (None, RecordedInputEvent::MouseMotion(event)),
+        WinitEvent::MouseWheel(event) => (Some(event.window), RecordedInputEvent::MouseWheel(event)),
+        WinitEvent::CursorMoved(event) => (Some(event.window), RecordedInputEvent::CursorMoved(event)),
+        WinitEvent::CursorEntered(event) => (Some(event.window), RecordedInputEvent::CursorEntered(event)),
+        WinitEvent::CursorLeft(event) => (Some(event.window), RecordedInputEvent::CursorLeft(event)),
+        WinitEvent::TouchInput(event) => (Some(event.window), RecordedInputEvent::Touch(event)),
+        _ => return None,
+    };
+    Some(recorded)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn record_input(
+    time: Res<Time<Real>>,
+    windows: NonSend<WinitWindows>,
+    mut events: EventReader<WinitEvent>,
+    mut recorder: ResMut<InputRecorder>,
+)
+{
+    let Some(started_at) = recorder.started_at else {
+        events.clear();
+        return;
+    };
+    let offset = time.elapsed().saturating_sub(started_at);
+
+    for event in events.read() {
+        let Some((window, event)) = input_from_winit_event(event) else { continue };
+
+        // Inputs for windows unknown to winit can't be mapped into other worlds.
+        let window = match window {
+            Some(window) => {
+                let Some(window_id) = windows.entity_to_winit.get(&window) else { continue };
+                Some(*window_id)
+            }
+            None => None,
+        };
+
+        recorder.log.entries.push(RecordedInput { offset, window, event });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn replay_input(world: &mut World)
+{
+    let now = world.resource::<Time<Real>>().elapsed();
+    let mut replay = world.resource_mut::<InputReplay>();
+    let started_at = *replay.started_at.get_or_insert(now);
+    let offset = now.saturating_sub(started_at);
+
+    // Collect inputs that are due.
+    let mut due = Vec::default();
+    while let Some(input) = replay.log.entries.get(replay.cursor) {
+        if input.offset > offset {
+            break;
+        }
+        due.push(input.clone());
+        replay.cursor += 1;
+    }
+
+    // Map the inputs' windows into this world and send them.
+    // - Inputs for windows that don't exist in this world are dropped.
+    for input in due {
+        let mut event = input.event;
+        if let Some(window_id) = input.window {
+            let windows = world.non_send_resource::<WinitWindows>();
+            let Some(entity) = windows.winit_to_entity.get(&window_id).copied() else { continue };
+            event.set_window(entity);
+        }
+        event.send(world);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An input event captured by [`InputRecorder`].
+#[derive(Debug, Clone)]
+pub enum RecordedInputEvent
+{
+    Keyboard(KeyboardInput),
+    MouseButton(MouseButtonInput),
+    MouseMotion(MouseMotion),
+    MouseWheel(MouseWheel),
+    CursorMoved(CursorMoved),
+    CursorEntered(CursorEntered),
+    CursorLeft(CursorLeft),
+    Touch(TouchInput),
+}
+
+impl RecordedInputEvent
+{
+    fn set_window(&mut self, window: Entity)
+    {
+        match self {
+            Self::Keyboard(event) => event.window = window,
+            Self::MouseButton(event) => event.window = window,
+            Self::MouseMotion(_) => (),
+            Self::MouseWheel(event) => event.window = window,
+            Self::CursorMoved(event) => event.window = window,
+            Self::CursorEntered(event) => event.window = window,
+            Self::CursorLeft(event) => event.window = window,
+            Self::Touch(event) => event.window = window,
+        }
+    }
+
+    fn send(self, world: &mut World)
+    {
+        match self {
+            Self::Keyboard(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::KeyboardInput(event));
+            }
+            Self::MouseButton(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::MouseButtonInput(event));
+            }
+            Self::MouseMotion(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::MouseMotion(event));
+            }
+            Self::MouseWheel(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::MouseWheel(event));
+            }
+            Self::CursorMoved(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::CursorMoved(event));
+            }
+            Self::CursorEntered(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::CursorEntered(event));
+            }
+            Self::CursorLeft(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::CursorLeft(event));
+            }
+            Self::Touch(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::TouchInput(event));
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+struct RecordedInput
+{
+    /// Time since recording started.
+    offset: Duration,
+    /// Windows are tracked by winit id so inputs can be mapped into any world.
+    window: Option<WindowId>,
+    event: RecordedInputEvent,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A timestamped log of input events recorded with [`InputRecorder`].
+#[derive(Debug, Clone, Default)]
+pub struct InputLog
+{
+    entries: Vec<RecordedInput>,
+}
+
+impl InputLog
+{
+    /// Gets the number of recorded inputs.
+    pub fn len(&self) -> usize
+    {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no inputs were recorded.
+    pub fn is_empty(&self) -> bool
+    {
+        self.entries.is_empty()
+    }
+
+    /// Gets the time between the start of recording and the last recorded input.
+    pub fn duration(&self) -> Duration
+    {
+        self.entries.last().map(|input| input.offset).unwrap_or_default()
+    }
+
+    /// Iterates the recorded inputs and their offsets from the start of recording.
+    pub fn iter(&self) -> impl Iterator<Item = (Duration, &RecordedInputEvent)> + '_
+    {
+        self.entries.iter().map(|input| (input.offset, &input.event))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for recording raw input received by a world while it is in the foreground.
+///
+/// Added by [`InputRecorderPlugin`].
+#[derive(Resource, Default)]
+pub struct InputRecorder
+{
+    started_at: Option<Duration>,
+    log: InputLog,
+}
+
+impl InputRecorder
+{
+    /// Starts recording. Discards any previous recording.
+    pub fn start(&mut self, time: &Time<Real>)
+    {
+        self.started_at = Some(time.elapsed());
+        self.log = InputLog::default();
+    }
+
+    /// Stops recording and returns the recorded inputs.
+    pub fn stop(&mut self) -> InputLog
+    {
+        self.started_at = None;
+        std::mem::take(&mut self.log)
+    }
+
+    /// Returns `true` if currently recording.
+    pub fn is_recording(&self) -> bool
+    {
+        self.started_at.is_some()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that replays an [`InputLog`] into a world.
+///
+/// Added by [`InputReplayPlugin`]. Replay starts the first time the world updates, so the log should be replayed
+/// into a world that is in the foreground (e.g. a freshly forked world).
+#[derive(Resource)]
+pub struct InputReplay
+{
+    log: InputLog,
+    cursor: usize,
+    started_at: Option<Duration>,
+}
+
+impl InputReplay
+{
+    /// Returns `true` if all inputs have been replayed.
+    pub fn is_finished(&self) -> bool
+    {
+        self.cursor >= self.log.len()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that adds [`InputRecorder`] to a windowed app.
+///
+/// Requires the `winit` feature.
+pub struct InputRecorderPlugin;
+
+impl Plugin for InputRecorderPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<InputRecorder>()
+            .add_systems(PreUpdate, record_input.run_if(in_foreground).before(InputSystem));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that replays an [`InputLog`] into a windowed app.
+///
+/// Inputs are sent at the same offsets they were recorded at, relative to the first time the app updates.
+///
+/// Requires the `winit` feature.
+pub struct InputReplayPlugin
+{
+    log: InputLog,
+}
+
+impl InputReplayPlugin
+{
+    pub fn new(log: InputLog) -> Self
+    {
+        Self { log }
+    }
+}
+
+impl Plugin for InputReplayPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.insert_resource(InputReplay { log: self.log.clone(), cursor: 0, started_at: None })
+            .add_systems(PreUpdate, replay_input.before(InputSystem));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
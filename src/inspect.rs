@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+type InspectFn = Box<dyn FnOnce(&World) + Send>;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs queued inspections against the background world.
+pub(crate) fn run_background_inspections(subapp_world: &World)
+{
+    let receiver = subapp_world.resource::<BackgroundInspectionReceiver>();
+    let background_app = subapp_world.non_send_resource::<BackgroundApp>();
+
+    while let Ok(inspect_fn) = receiver.0.try_recv() {
+        // Inspections are dropped if there is no background world, which disconnects their result channels.
+        let Some(background_app) = background_app.app.as_ref() else { continue };
+        (inspect_fn)(&background_app.world);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for borrowing the background world from other worlds, for debugging.
+///
+/// Inspections are run by the `bevy_worldswap` backend between frames, after swap commands are applied.
+///
+/// Requires the `inspect` feature.
+#[derive(Resource, Clone)]
+pub struct BackgroundInspector(pub(crate) BoundedSender<InspectFn>);
+
+impl BackgroundInspector
+{
+    /// Queues a closure that will borrow the background world immutably.
+    ///
+    /// The closure's output is sent to the returned receiver. If there is no background world when the closure
+    /// would run, then the closure is dropped and the receiver is disconnected.
+    pub fn inspect<R: Send + 'static>(
+        &self,
+        inspect_fn: impl FnOnce(&World) -> R + Send + 'static,
+    ) -> crossbeam::channel::Receiver<R>
+    {
+        let (sender, receiver) = crossbeam::channel::bounded(1);
+        // Ignore errors.
+        let _ = self.0.send(Box::new(move |world: &World| {
+            let _ = sender.send((inspect_fn)(world));
+        }));
+        receiver
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for receiving inspections sent with [`BackgroundInspector`].
+///
+/// Only used in [`WorldSwapSubApp`](crate::WorldSwapSubApp).
+#[derive(Resource)]
+pub(crate) struct BackgroundInspectionReceiver(pub(crate) BoundedReceiver<InspectFn>);
+
+//-------------------------------------------------------------------------------------------------------------------
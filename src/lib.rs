@@ -5,21 +5,144 @@ use crate as bevy_worldswap;
 
 //module tree
 mod app;
+mod asset_handoff;
+mod attention;
+mod background_safe;
+mod bootstrap;
+mod channel_bound;
+mod channels;
+mod determinism;
+mod entity_transfer;
+mod errors;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(all(feature = "render", feature = "winit"))]
+mod frame_capture;
+#[cfg(feature = "gizmos")]
+mod gizmos;
+#[cfg(feature = "hotkeys")]
+mod hotkeys;
+mod idle;
+#[cfg(feature = "winit")]
+mod input_record;
+#[cfg(feature = "inspect")]
+mod inspect;
+mod middleware;
+mod minigame;
+mod non_send_handoff;
+mod panic_context;
+mod pending_swap;
+#[cfg(feature = "play_mode")]
+mod play_mode;
 mod plugins;
+mod profiling;
+mod registry;
+mod remote;
+#[cfg(feature = "render")]
 mod render_worker;
+mod rpc;
 mod run_conditions;
+mod shared_store;
+#[cfg(feature = "states")]
+mod state_transfer;
 mod subapp;
+#[cfg(feature = "metrics")]
+mod swap_metrics;
+#[cfg(feature = "winit")]
+mod window_geometry;
+#[cfg(feature = "winit")]
 mod window_utils;
+#[cfg(feature = "world_diff")]
+mod world_diff;
 
 //API exports
+pub(crate) use crate::background_safe::run_main_with_schedule_filter;
+pub(crate) use crate::channel_bound::{BoundedReceiver, BoundedSender, SendFailure};
+pub(crate) use crate::determinism::install_world_rng;
+#[cfg(all(feature = "render", feature = "winit"))]
+pub(crate) use crate::frame_capture::{capture_frame, FrameCaptureState};
+#[cfg(feature = "gizmos")]
+pub(crate) use crate::gizmos::{disable_gizmos, enable_gizmos};
+#[cfg(feature = "hotkeys")]
+pub(crate) use crate::hotkeys::background_updates_paused;
+pub(crate) use crate::idle::{
+    begin_idle_tracking, end_idle_tracking, is_idle_parked, register_idle_wake_check, reset_idle_parking,
+};
+pub(crate) use crate::middleware::run_swap_middleware;
+pub(crate) use crate::minigame::deliver_minigame_result;
+pub(crate) use crate::non_send_handoff::run_non_send_handoffs;
+pub(crate) use crate::panic_context::{install_panic_hook, set_panic_context};
+#[cfg(feature = "play_mode")]
+pub(crate) use crate::play_mode::run_play_mode_copy_back;
+pub(crate) use crate::profiling::{
+    begin_frame_profile, finish_frame_profile, record_profile_background, record_profile_stage, FrameProfiler,
+    ProfileStage,
+};
+pub(crate) use crate::registry::{sync_world_registry, WorldName, WorldRegistrySynced};
+pub(crate) use crate::remote::sync_remote_registry;
+#[cfg(feature = "states")]
+pub(crate) use crate::state_transfer::run_state_transfers;
 pub(crate) use crate::prelude::*;
 pub(crate) use crate::subapp::*;
+#[cfg(feature = "metrics")]
+pub(crate) use crate::swap_metrics::{
+    record_background_tick, record_command_applied, record_command_error, CommandTimer,
+};
+#[cfg(feature = "winit")]
+pub(crate) use crate::window_geometry::{apply_window_geometry, record_window_geometry};
+#[cfg(feature = "winit")]
 pub(crate) use crate::window_utils::*;
+#[cfg(feature = "world_diff")]
+pub(crate) use crate::world_diff::{take_fork_snapshot, TakeForkSnapshot};
 
 pub mod prelude
 {
     pub use crate::app::*;
+    pub use crate::asset_handoff::*;
+    pub use crate::attention::*;
+    pub use crate::background_safe::{BackgroundSafePlugin, BackgroundSystemPolicy};
+    pub use crate::bootstrap::*;
+    pub use crate::channel_bound::{ChannelBound, OverflowPolicy};
+    pub use crate::channels::{ChannelMessage, ChannelSender, WorldSwapChannels};
+    pub use crate::determinism::{DeterminismPlugin, WorldRng, WorldSeed};
+    pub use crate::entity_transfer::*;
+    pub use crate::errors::*;
+    #[cfg(feature = "ffi")]
+    pub use crate::ffi::*;
+    #[cfg(all(feature = "render", feature = "winit"))]
+    pub use crate::frame_capture::FrameCapture;
+    #[cfg(feature = "hotkeys")]
+    pub use crate::hotkeys::{SwapHotkeys, SwapHotkeysPlugin};
+    pub use crate::idle::IdleParking;
+    #[cfg(feature = "winit")]
+    pub use crate::input_record::*;
+    #[cfg(feature = "inspect")]
+    pub use crate::inspect::*;
+    pub use crate::middleware::{StagePosition, SwapMiddleware, SwapMiddlewareFn, SwapStage};
+    pub use crate::minigame::{MinigameBuilder, MinigameResult, MinigameWorld};
+    pub use crate::non_send_handoff::{NonSendHandoffHook, NonSendHandoffPlugin};
+    pub use crate::panic_context::{WorldCrashHandler, WorldPanicInfo};
+    pub use crate::pending_swap::*;
+    #[cfg(feature = "play_mode")]
+    pub use crate::play_mode::{PlayMode, PlayModeCopyBackFn, PlayModeEntityMap};
     pub use crate::plugins::*;
+    pub use crate::profiling::{WorldSwapFrameTimings, WorldTiming};
+    pub use crate::registry::{WorldLabel, WorldRegistry};
+    pub use crate::remote::WorldSwapRemoteHandle;
+    #[cfg(feature = "render")]
     pub use crate::render_worker::*;
+    pub use crate::rpc::*;
     pub use crate::run_conditions::*;
+    pub use crate::shared_store::*;
+    #[cfg(feature = "states")]
+    pub use crate::state_transfer::StateTransferPlugin;
+    #[cfg(feature = "winit")]
+    pub use crate::window_geometry::{WindowGeometry, WindowGeometryPlugin, WindowGeometryProfile};
+    #[cfg(feature = "winit")]
+    pub use crate::window_utils::{
+        CachedWindowEventKind, DeferredWindowCloseRequested, ReopenClosedWindows, StaleWindowFn,
+        StaleWindowHandler, WindowEventCache,
+    };
+    #[cfg(feature = "world_diff")]
+    pub use crate::world_diff::{ForkSnapshot, ResourceDiff};
 }
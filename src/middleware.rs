@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stages of the `bevy_worldswap` backend's tick, in the order they run.
+///
+/// The backend ticks after the foreground world's `Main` schedule.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SwapStage
+{
+    /// `AppExit` events in the foreground world are converted to [`SwapCommand::Join`] if there is a background
+    /// world.
+    InterceptExit,
+    /// The [`SwapCommand`] to apply this tick is selected (see [`CommandArbitration`]).
+    ReceiveCommands,
+    /// The selected command is applied, then attention requests are forwarded to the foreground world.
+    ApplySwap,
+    /// The foreground world is extracted into its render app, and ordered sub apps run.
+    ///
+    /// Nothing is rendered on ticks where worlds are swapped, but middleware for this stage still runs.
+    Render,
+    /// The background world is updated according to its [`BackgroundTickRate`].
+    ///
+    /// This stage and its middleware are skipped on ticks where worlds are swapped.
+    BackgroundUpdate,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Where [`SwapMiddleware`] runs relative to its [`SwapStage`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum StagePosition
+{
+    Before,
+    After,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Callback run by the `bevy_worldswap` backend around a [`SwapStage`].
+///
+/// The first argument is the backend's world, which contains [`WorldSwapPlugin`], [`SwapCommandSender`], and
+/// [`WorldRegistry`]. The second argument is the foreground world.
+pub type SwapMiddlewareFn = fn(&mut World, &mut World);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Callbacks that customize the `bevy_worldswap` backend's tick. See [`WorldSwapPlugin::middleware`].
+///
+/// Middleware for the same stage and position runs in the order it was added.
+#[derive(Clone, Default)]
+pub struct SwapMiddleware
+{
+    hooks: Vec<(SwapStage, StagePosition, SwapMiddlewareFn)>,
+}
+
+impl SwapMiddleware
+{
+    /// Adds a callback that runs before a stage.
+    pub fn before(mut self, stage: SwapStage, callback: SwapMiddlewareFn) -> Self
+    {
+        self.hooks.push((stage, StagePosition::Before, callback));
+        self
+    }
+
+    /// Adds a callback that runs after a stage.
+    pub fn after(mut self, stage: SwapStage, callback: SwapMiddlewareFn) -> Self
+    {
+        self.hooks.push((stage, StagePosition::After, callback));
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs middleware registered for a stage.
+pub(crate) fn run_swap_middleware(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    stage: SwapStage,
+    position: StagePosition,
+)
+{
+    // Index the hooks so the plugin resource isn't borrowed while callbacks run.
+    let count = subapp_world.resource::<WorldSwapPlugin>().middleware.hooks.len();
+    for i in 0..count {
+        let Some((hook_stage, hook_position, callback)) =
+            subapp_world.resource::<WorldSwapPlugin>().middleware.hooks.get(i).copied()
+        else {
+            return;
+        };
+        if hook_stage != stage || hook_position != position {
+            continue;
+        }
+        (callback)(subapp_world, main_world);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,111 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Moves a minigame's result into the world it joined.
+#[derive(Resource, Copy, Clone)]
+struct MinigameDelivery(fn(&mut World, &mut World));
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn deliver_result<T: Send + Sync + 'static>(minigame: &mut World, parent: &mut World)
+{
+    let Some(result) = minigame.remove_resource::<MinigameResult<T>>() else { return };
+    parent.insert_resource(result);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Delivers the result of a joined minigame world to the world it joined.
+pub(crate) fn deliver_minigame_result(joined: &mut World, parent: &mut World)
+{
+    let Some(delivery) = joined.get_resource::<MinigameDelivery>().copied() else { return };
+    (delivery.0)(joined, parent);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource with the outcome of a minigame world made with [`MinigameWorld::builder`].
+///
+/// Insert this into the minigame world before it exits. When the minigame joins, the resource is moved into the
+/// parent world (before [`WorldSwapPlugin::swap_join_recovery`] runs), where it can be detected with
+/// [`resource_added`] and removed once handled.
+#[derive(Resource, Debug, Clone, Deref, DerefMut)]
+pub struct MinigameResult<T: Send + Sync + 'static>(pub T);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Preset for short-lived worlds that are forked from a parent world and deliver a typed result when they end.
+///
+/// A minigame ends by sending [`AppExit`], which is converted to [`SwapCommand::Join`] since the parent is in the
+/// background. Its [`MinigameResult<T>`] is then moved into the parent world.
+pub struct MinigameWorld;
+
+impl MinigameWorld
+{
+    /// Makes a builder for a minigame with result type `T`.
+    pub fn builder<T: Send + Sync + 'static>(app: App) -> MinigameBuilder<T>
+    {
+        MinigameBuilder { app, background_tick_rate: None, warmup_ticks: 0, _phantom: PhantomData }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Builder for minigame worlds. See [`MinigameWorld`].
+pub struct MinigameBuilder<T: Send + Sync + 'static>
+{
+    app: App,
+    background_tick_rate: Option<BackgroundTickRate>,
+    warmup_ticks: u32,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + Sync + 'static> MinigameBuilder<T>
+{
+    /// Sets the minigame world's tick rate for when it is in the background.
+    ///
+    /// See [`WorldSwapApp::new_with`].
+    pub fn background_tick_rate(mut self, tick_rate: BackgroundTickRate) -> Self
+    {
+        self.background_tick_rate = Some(tick_rate);
+        self
+    }
+
+    /// Sets the minigame world's warm-up ticks.
+    ///
+    /// See [`WorldSwapApp::with_warmup_ticks`].
+    pub fn warmup_ticks(mut self, ticks: u32) -> Self
+    {
+        self.warmup_ticks = ticks;
+        self
+    }
+
+    /// Makes the minigame world.
+    ///
+    /// ## Panics
+    /// See [`WorldSwapApp::new`].
+    pub fn build(self) -> WorldSwapApp
+    {
+        let mut app = match self.background_tick_rate {
+            Some(tick_rate) => WorldSwapApp::new_with(self.app, tick_rate),
+            None => WorldSwapApp::new(self.app),
+        };
+        app.world.insert_resource(MinigameDelivery(deliver_result::<T>));
+        app.with_warmup_ticks(self.warmup_ticks)
+    }
+
+    /// Makes the minigame world and forks it into the foreground.
+    ///
+    /// Returns the id of the [`SwapCommand::Fork`] command.
+    pub fn fork(self, sender: &SwapCommandSender) -> SwapCommandId
+    {
+        sender.send(SwapCommand::Fork(self.build()))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,96 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+type NonSendHandoffFn = Box<dyn Fn(&mut World, &mut World) + Send + Sync>;
+
+/// Hook called on the incoming world after a non-send resource is handed to it.
+pub type NonSendHandoffHook<T> = fn(&mut T, &mut World);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Non-send handoffs registered in a world by [`NonSendHandoffPlugin`].
+#[derive(Resource, Default)]
+struct NonSendHandoffs(Vec<NonSendHandoffFn>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn handoff_non_send<T: 'static>(outgoing: &mut World, incoming: &mut World, hook: Option<NonSendHandoffHook<T>>)
+{
+    let Some(mut resource) = outgoing.remove_non_send_resource::<T>() else { return };
+    if let Some(hook) = hook {
+        (hook)(&mut resource, incoming);
+    }
+    incoming.insert_non_send_resource(resource);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs non-send handoffs registered in the incoming world.
+pub(crate) fn run_non_send_handoffs(outgoing: &mut World, incoming: &mut World)
+{
+    let Some(handoffs) = incoming.remove_resource::<NonSendHandoffs>() else { return };
+    for handoff in handoffs.0.iter() {
+        (handoff)(outgoing, incoming);
+    }
+    incoming.insert_resource(handoffs);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that moves the non-send resource `T` from the outgoing foreground world into this world when it enters
+/// the foreground.
+///
+/// This is useful for resources that own a device and can only exist once, such as the audio manager of an audio
+/// backend other than `bevy_audio` (e.g. `kira` or `oddio`). Moving the manager lets sounds keep playing across
+/// swaps. If this world already has a `T`, it is replaced. Nothing is moved if the outgoing world doesn't have a
+/// `T`.
+///
+/// Use [`Self::with_hook`] to adjust the resource for this world after it is moved (e.g. to crossfade music).
+///
+/// Add this to every world that uses the resource so it follows the foreground world.
+pub struct NonSendHandoffPlugin<T: 'static>
+{
+    hook: Option<NonSendHandoffHook<T>>,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static> NonSendHandoffPlugin<T>
+{
+    /// Makes a new plugin.
+    pub fn new() -> Self
+    {
+        Self { hook: None, _phantom: PhantomData }
+    }
+
+    /// Sets a hook that is called with the resource and this world after the resource is moved into this world.
+    pub fn with_hook(mut self, hook: NonSendHandoffHook<T>) -> Self
+    {
+        self.hook = Some(hook);
+        self
+    }
+}
+
+impl<T: 'static> Default for NonSendHandoffPlugin<T>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<T: 'static> Plugin for NonSendHandoffPlugin<T>
+{
+    fn build(&self, app: &mut App)
+    {
+        let hook = self.hook;
+        app.world_mut()
+            .get_resource_or_insert_with(NonSendHandoffs::default)
+            .0
+            .push(Box::new(move |outgoing, incoming| handoff_non_send::<T>(outgoing, incoming, hook)));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
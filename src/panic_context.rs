@@ -0,0 +1,88 @@
+use std::sync::{Mutex, Once};
+
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::ecs::world::WorldId;
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The world that is currently updating, along with its crash handler.
+///
+/// Only one world's schedule is run at a time by the main thread, so a global context is sufficient.
+static PANIC_CONTEXT: Mutex<Option<(WorldPanicInfo, Option<WorldCrashHandler>)>> = Mutex::new(None);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records that `world` is about to run `schedule`.
+pub(crate) fn set_panic_context(world: &World, schedule: impl ScheduleLabel)
+{
+    let info = WorldPanicInfo {
+        world: world.id(),
+        label: world.get_resource::<WorldLabel>().cloned(),
+        status: world.get_resource::<WorldSwapStatus>().copied(),
+        schedule: schedule.intern(),
+    };
+    let handler = world.get_resource::<WorldCrashHandler>().copied();
+
+    let Ok(mut context) = PANIC_CONTEXT.lock() else { return };
+    *context = Some((info, handler));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Installs a panic hook that reports the current world's panic context before calling the previous panic hook.
+///
+/// The hook is only installed once per process.
+pub(crate) fn install_panic_hook()
+{
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let prev_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            // Use try_lock in case the panic happened while the context was being set.
+            if let Ok(context) = PANIC_CONTEXT.try_lock() {
+                if let Some((info, handler)) = context.as_ref() {
+                    let world = WorldName { id: info.world, label: info.label.as_ref() };
+                    tracing::error!("panic in {:?} ({:?}) while running {:?}", world, info.status, info.schedule);
+                    if let Some(handler) = handler {
+                        (handler.0)(info);
+                    }
+                }
+            }
+
+            prev_hook(panic_info);
+        }));
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Information about the world that was updating when a panic occurred.
+///
+/// Note that panics in a world's `RenderApp` may be reported with the context of whichever world is updating on
+/// the main thread at the time, since pipelined rendering runs in parallel.
+#[derive(Debug, Clone)]
+pub struct WorldPanicInfo
+{
+    /// The id of the world.
+    pub world: WorldId,
+    /// The world's [`WorldLabel`], if it has one.
+    pub label: Option<WorldLabel>,
+    /// The world's status, if it has one.
+    pub status: Option<WorldSwapStatus>,
+    /// The schedule being run.
+    pub schedule: InternedScheduleLabel,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for handling panics that occur while this world is updating.
+///
+/// The handler is called by the panic hook installed by [`WorldSwapPlugin`], before the previous panic hook.
+#[derive(Resource, Copy, Clone)]
+pub struct WorldCrashHandler(pub fn(&WorldPanicInfo));
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,157 @@
+use std::sync::Mutex;
+
+use bevy::asset::{RecursiveDependencyLoadState, UntypedAssetId};
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sends the pending swap command once its assets are loaded and its condition is satisfied.
+fn apply_pending_swap(world: &mut World)
+{
+    let Some(pending) = world.get_resource::<PendingSwap>() else { return };
+    if pending.state == PendingSwapState::Failed {
+        return;
+    }
+
+    // Check assets.
+    let (loaded, failed) = match world.get_resource::<AssetServer>() {
+        Some(asset_server) => pending.check_assets(asset_server),
+        None => (true, false),
+    };
+    if failed {
+        tracing::error!("failed loading assets for pending swap command; the command will not be sent");
+        world.resource_mut::<PendingSwap>().state = PendingSwapState::Failed;
+        return;
+    }
+    if !loaded {
+        return;
+    }
+
+    // Check the readiness condition.
+    if let Some(condition) = pending.condition {
+        if !(condition)(world) {
+            return;
+        }
+    }
+
+    // Send the command.
+    let pending = world.remove_resource::<PendingSwap>().unwrap();
+    let Some(command) = pending.command.into_inner().ok().flatten() else { return };
+    world.resource::<SwapCommandSender>().send(command);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The state of a [`PendingSwap`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PendingSwapState
+{
+    /// Waiting for assets to load and for the readiness condition.
+    Waiting,
+    /// An asset failed to load. The command will not be sent.
+    Failed,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that holds a [`SwapCommand`] until a set of assets are loaded.
+///
+/// Insert this into the foreground world to queue a command (e.g. a [`SwapCommand::Pass`] to a level whose assets
+/// are being loaded by a shared [`AssetServer`]). The command is sent once all handles are loaded with their
+/// dependencies and the optional readiness condition returns `true`. The resource is then removed.
+///
+/// If an asset fails to load, the state changes to [`PendingSwapState::Failed`] and the command is not sent.
+///
+/// Pending swaps are processed in [`Last`] by [`PendingSwapPlugin`].
+#[derive(Resource)]
+pub struct PendingSwap
+{
+    command: Mutex<Option<SwapCommand>>,
+    handles: Vec<UntypedAssetId>,
+    condition: Option<fn(&World) -> bool>,
+    state: PendingSwapState,
+}
+
+impl PendingSwap
+{
+    /// Makes a pending swap for a command.
+    ///
+    /// Without handles or a condition, the command is sent the next time pending swaps are processed.
+    pub fn new(command: SwapCommand) -> Self
+    {
+        Self {
+            command: Mutex::new(Some(command)),
+            handles: Vec::default(),
+            condition: None,
+            state: PendingSwapState::Waiting,
+        }
+    }
+
+    /// Adds an asset that must be loaded before the command is sent.
+    ///
+    /// The caller should keep the handle alive (e.g. by storing it in the incoming world) until the swap is
+    /// applied.
+    pub fn with_handle(mut self, handle: impl Into<UntypedHandle>) -> Self
+    {
+        self.handles.push(handle.into().id());
+        self
+    }
+
+    /// Adds a readiness condition that must return `true` before the command is sent.
+    pub fn with_condition(mut self, condition: fn(&World) -> bool) -> Self
+    {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Gets the pending swap's state.
+    pub fn state(&self) -> PendingSwapState
+    {
+        self.state
+    }
+
+    /// Gets the number of loaded assets and the total number of assets.
+    pub fn progress(&self, asset_server: &AssetServer) -> (usize, usize)
+    {
+        let loaded = self
+            .handles
+            .iter()
+            .filter(|id| asset_server.is_loaded_with_dependencies(**id))
+            .count();
+        (loaded, self.handles.len())
+    }
+
+    /// Returns `(all loaded, any failed)`.
+    fn check_assets(&self, asset_server: &AssetServer) -> (bool, bool)
+    {
+        let mut loaded = true;
+        for id in self.handles.iter() {
+            match asset_server.get_recursive_dependency_load_state(*id) {
+                Some(RecursiveDependencyLoadState::Loaded) => (),
+                Some(RecursiveDependencyLoadState::Failed { .. }) => return (false, true),
+                _ => loaded = false,
+            }
+        }
+        (loaded, false)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that processes [`PendingSwap`].
+///
+/// Added automatically by [`WorldSwapPlugin`], [`ChildDefaultPlugins`](crate::ChildDefaultPlugins), and
+/// [`ChildWindowPlugins`](crate::ChildWindowPlugins). Headless child apps can add it manually.
+pub struct PendingSwapPlugin;
+
+impl Plugin for PendingSwapPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.add_systems(Last, apply_pending_swap.run_if(in_foreground));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,110 @@
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+use bevy::scene::DynamicSceneBuilder;
+#[cfg(feature = "winit")]
+use bevy::window::Window;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Hook for copying selected changes from a play-mode world back into its edit world.
+///
+/// Called with the play-mode world and the edit world when the play-mode world joins.
+pub type PlayModeCopyBackFn = fn(&mut World, &mut World);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Copy-back hook registered in a play-mode world.
+#[derive(Resource, Copy, Clone)]
+struct PlayModeCopyBack(PlayModeCopyBackFn);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs the copy-back hook of a joined play-mode world.
+pub(crate) fn run_play_mode_copy_back(joined: &mut World, edit: &mut World)
+{
+    let Some(copy_back) = joined.get_resource::<PlayModeCopyBack>().copied() else { return };
+    (copy_back.0)(joined, edit);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource in a play-mode world that maps entities in the edit world to their copies in the play-mode world.
+///
+/// Useful in [`PlayModeCopyBackFn`] hooks.
+#[derive(Resource, Debug, Default, Clone, Deref)]
+pub struct PlayModeEntityMap(pub EntityHashMap<Entity>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Helper for editor-like apps that test their content in a disposable "play mode" world.
+///
+/// The play-mode world is made from a [`DynamicScene`](bevy::scene::DynamicScene) snapshot of the edit world's
+/// entities, then forked into the foreground. While playing, [`SwapCommand::Swap`] toggles between the play-mode
+/// and edit worlds. Sending [`SwapCommand::Join`] (or [`AppExit`]) from the play-mode world returns to the edit
+/// world and discards play-mode changes, except for those copied back by the [`PlayModeCopyBackFn`] hook.
+///
+/// Only reflected components registered in the edit world's [`AppTypeRegistry`] are copied, and resources are not
+/// copied. Window entities are skipped since windows belong to the foreground world.
+///
+/// Requires the `play_mode` feature.
+pub struct PlayMode
+{
+    app: WorldSwapApp,
+}
+
+impl PlayMode
+{
+    /// Makes a play-mode world by copying the edit world's entities into a fresh [`App`].
+    ///
+    /// The app should have the same plugins as the edit world's app so the copied components are registered. This
+    /// calls [`WorldSwapApp::new`] on the app.
+    pub fn snapshot(edit_world: &World, app: App) -> Self
+    {
+        let entities = edit_world.iter_entities().filter_map(|entity| {
+            #[cfg(feature = "winit")]
+            if entity.contains::<Window>() {
+                return None;
+            }
+            Some(entity.id())
+        });
+        let scene = DynamicSceneBuilder::from_world(edit_world)
+            .extract_entities(entities)
+            .build();
+
+        let mut app = WorldSwapApp::new(app);
+        let mut entity_map = EntityHashMap::default();
+        if let Err(err) = scene.write_to_world(&mut app.world, &mut entity_map) {
+            tracing::error!("failed copying edit world into play-mode world: {err}");
+        }
+        app.world.insert_resource(PlayModeEntityMap(entity_map));
+
+        Self { app }
+    }
+
+    /// Sets a hook for copying selected changes back into the edit world when the play-mode world joins.
+    ///
+    /// The hook runs before [`WorldSwapPlugin::swap_join_recovery`].
+    pub fn with_copy_back(mut self, copy_back: PlayModeCopyBackFn) -> Self
+    {
+        self.app.world.insert_resource(PlayModeCopyBack(copy_back));
+        self
+    }
+
+    /// Gets the play-mode world.
+    pub fn build(self) -> WorldSwapApp
+    {
+        self.app
+    }
+
+    /// Forks the play-mode world into the foreground.
+    ///
+    /// Returns the id of the [`SwapCommand::Fork`] command.
+    pub fn fork(self, sender: &SwapCommandSender) -> SwapCommandId
+    {
+        sender.send(SwapCommand::Fork(self.app))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,102 +1,210 @@
+use std::num::NonZeroU8;
+#[cfg(feature = "winit")]
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use bevy::a11y::Focus;
-use bevy::app::{PluginGroupBuilder, SubApp};
-use bevy::ecs::schedule::ScheduleLabel;
+#[cfg(feature = "winit")]
+use bevy::a11y::{AccessibilityPlugin, Focus};
+#[cfg(feature = "winit")]
+use bevy::app::PluginGroupBuilder;
+use bevy::app::{InternedAppLabel, SubApp};
+#[cfg(feature = "winit")]
+use bevy::core::{FrameCountPlugin, TaskPoolPlugin, TypeRegistrationPlugin};
+#[cfg(feature = "winit")]
+use bevy::diagnostic::DiagnosticsPlugin;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+#[cfg(feature = "winit")]
+use bevy::ecs::system::SystemParam;
+#[cfg(feature = "winit")]
+use bevy::hierarchy::HierarchyPlugin;
+#[cfg(feature = "winit")]
+use bevy::input::InputPlugin;
+#[cfg(all(feature = "render", feature = "winit"))]
 use bevy::log::LogPlugin;
 use bevy::prelude::*;
+#[cfg(feature = "render")]
 use bevy::render::pipelined_rendering::RenderExtractApp;
-use bevy::render::renderer::{RenderAdapter, RenderAdapterInfo, RenderDevice, RenderInstance, RenderQueue};
-use bevy::render::settings::RenderCreation;
-use bevy::render::{RenderApp, RenderPlugin};
+#[cfg(all(feature = "render", feature = "winit"))]
+use bevy::render::renderer::{RenderAdapter, RenderAdapterInfo, RenderDevice, RenderQueue};
+#[cfg(feature = "render")]
+use bevy::render::renderer::RenderInstance;
+#[cfg(all(feature = "render", feature = "winit"))]
+use bevy::render::settings::{RenderCreation, WgpuSettings};
+#[cfg(feature = "render")]
+use bevy::render::RenderApp;
+#[cfg(all(feature = "render", feature = "winit"))]
+use bevy::render::RenderPlugin;
+#[cfg(feature = "winit")]
+use bevy::time::TimePlugin;
 use bevy::time::TimeSender;
+#[cfg(feature = "winit")]
+use bevy::transform::TransformPlugin;
+#[cfg(feature = "winit")]
 use bevy::window::{
-    ExitCondition, PrimaryWindow, WindowBackendScaleFactorChanged, WindowScaleFactorChanged, WindowThemeChanged,
+    ExitCondition, PrimaryWindow, WindowBackendScaleFactorChanged, WindowCloseRequested, WindowFocused,
+    WindowMoved, WindowOccluded, WindowResized, WindowScaleFactorChanged, WindowThemeChanged,
 };
-use bevy::winit::{WinitCorePlugin, WinitPlugin};
+#[cfg(feature = "winit")]
+use bevy::winit::{WinitCorePlugin, WinitWindows};
+#[cfg(all(feature = "render", feature = "winit"))]
+use bevy::winit::WinitPlugin;
 
 use crate::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Window lifecycle event readers used by [`collect_window_events`].
+#[cfg(feature = "winit")]
+#[derive(SystemParam)]
+struct WindowEventReaders<'w, 's>
+{
+    backend_scale_factor: EventReader<'w, 's, WindowBackendScaleFactorChanged>,
+    scale_factor: EventReader<'w, 's, WindowScaleFactorChanged>,
+    theme: EventReader<'w, 's, WindowThemeChanged>,
+    resized: EventReader<'w, 's, WindowResized>,
+    moved: EventReader<'w, 's, WindowMoved>,
+    focused: EventReader<'w, 's, WindowFocused>,
+    occluded: EventReader<'w, 's, WindowOccluded>,
+    close_requested: EventReader<'w, 's, WindowCloseRequested>,
+}
+
+#[cfg(feature = "winit")]
 fn collect_window_events(
     windows: Query<(), With<Window>>,
+    winit_windows: NonSend<WinitWindows>,
     mut removed_windows: RemovedComponents<Window>,
-    mut backend_scale_factor_events: EventReader<WindowBackendScaleFactorChanged>,
-    mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
-    mut theme_events: EventReader<WindowThemeChanged>,
+    mut events: WindowEventReaders,
     mut event_cache: ResMut<WindowEventCache>,
+    mut event_stamps: ResMut<WindowEventStamps>,
 )
 {
     // Clean up existing entries to avoid memory leak for spawing/despawning windows.
-    for removed in removed_windows.read() {
-        if windows.contains(removed) {
+    if removed_windows.read().count() > 0 {
+        event_cache.retain_windows(&winit_windows);
+        event_stamps.retain_windows(&winit_windows);
+    }
+
+    // Collect events in order.
+    // - Events are read per type, so ordering is only preserved across ticks and within each type. This is
+    //   sufficient for reconstructing the latest window state.
+    for event in events.backend_scale_factor.read() {
+        if !windows.contains(event.window) {
+            continue;
+        }
+        event_cache.insert_backend_scale_factor_event(&winit_windows, &mut event_stamps, event.clone());
+    }
+
+    for event in events.scale_factor.read() {
+        if !windows.contains(event.window) {
+            continue;
+        }
+        event_cache.insert_scale_factor_event(&winit_windows, &mut event_stamps, event.clone());
+    }
+
+    for event in events.theme.read() {
+        if !windows.contains(event.window) {
+            continue;
+        }
+        event_cache.insert_theme_event(&winit_windows, &mut event_stamps, event.clone());
+    }
+
+    for event in events.resized.read() {
+        if !windows.contains(event.window) {
             continue;
         }
-        event_cache.remove(removed);
+        event_cache.insert_resized_event(&winit_windows, &mut event_stamps, event.clone());
     }
 
-    // Collect events.
-    for event in backend_scale_factor_events.read() {
+    for event in events.moved.read() {
         if !windows.contains(event.window) {
             continue;
         }
-        event_cache.insert_backend_scale_factor_event(event.clone());
+        event_cache.insert_moved_event(&winit_windows, &mut event_stamps, event.clone());
     }
 
-    for event in scale_factor_events.read() {
+    for event in events.focused.read() {
         if !windows.contains(event.window) {
             continue;
         }
-        event_cache.insert_scale_factor_event(event.clone());
+        event_cache.insert_focused_event(&winit_windows, &mut event_stamps, event.clone());
     }
 
-    for event in theme_events.read() {
+    for event in events.occluded.read() {
         if !windows.contains(event.window) {
             continue;
         }
-        event_cache.insert_theme_event(event.clone());
+        event_cache.insert_occluded_event(&winit_windows, &mut event_stamps, event.clone());
     }
+
+    for event in events.close_requested.read() {
+        if !windows.contains(event.window) {
+            continue;
+        }
+        event_cache.insert_close_requested_event(&winit_windows, &mut event_stamps, event.clone());
+    }
+
+    // Events dispatched into this world were collected above if they were going to be.
+    event_stamps.clear_echoes();
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+#[cfg(all(feature = "render", feature = "winit"))]
 struct RenderPluginFollowUp
 {
     target: RenderWorkerTarget,
+    swap_commands: SwapCommandSender,
+    compute_only: bool,
 }
 
+#[cfg(all(feature = "render", feature = "winit"))]
 impl RenderPluginFollowUp
 {
-    fn new(target: RenderWorkerTarget) -> Self
+    fn new(target: RenderWorkerTarget, swap_commands: SwapCommandSender, compute_only: bool) -> Self
     {
-        Self { target }
+        Self { target, swap_commands, compute_only }
     }
 }
 
+#[cfg(all(feature = "render", feature = "winit"))]
 impl Plugin for RenderPluginFollowUp
 {
     fn build(&self, app: &mut App)
     {
-        let world_id = RenderWorkerId::from(app.world());
+        let worker_id = self.target.new_worker_id();
+        // Commands sent from the render world are attributed to the world that owns it.
+        let swap_commands = self.swap_commands.with_source(Some(app.world().id()));
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             tracing::warn!("RenderApp missing in RenderPluginFollowUp");
             return;
         };
-        render_app.add_plugins(RenderWorkerPlugin {
-            worker: RenderWorker { id: world_id, target: self.target.clone() },
-        });
+        render_app.insert_resource(swap_commands);
+        if !self.compute_only {
+            render_app.add_plugins(RenderWorkerPlugin {
+                worker: RenderWorker { id: worker_id, target: self.target.clone() },
+            });
+        }
         let time_sender = render_app
             .world()
             .get_resource::<TimeSender>()
-            .expect("RenderPlugin is missing TimeSender");
-        let time_sender = TimeSender(time_sender.0.clone());
+            .map(|time_sender| TimeSender(time_sender.0.clone()));
 
-        // We save the target in this world so it can be used to make new apps.
+        // We save the target in this world so it can be used to make new apps, and save the worker id so the
+        // worldswap subapp can tell when this world's renderer is running.
+        // - Compute-only worlds don't have a worker id since their renderer doesn't use the target.
         app.insert_resource(self.target.clone());
+        if self.compute_only {
+            app.insert_resource(ComputeOnly);
+        } else {
+            app.insert_resource(worker_id);
+        }
 
         // We save the TimeSender so it can be extracted into WorldSwapApp.
-        app.insert_resource(time_sender);
+        // - Custom render backends may not install time channels, in which case the world's time is driven by its
+        //   own clock.
+        if let Some(time_sender) = time_sender {
+            app.insert_resource(time_sender);
+        }
     }
 }
 
@@ -104,12 +212,14 @@ impl Plugin for RenderPluginFollowUp
 
 /// Plugin for inserting an asset server as a resource.
 ///
-/// Used in ChildDefaultPlugins.
+/// Used in ChildDefaultPlugins and ChildWindowPlugins.
+#[cfg(feature = "winit")]
 struct InsertAssetServerPlugin
 {
     asset_server: Arc<Mutex<Option<AssetServer>>>,
 }
 
+#[cfg(feature = "winit")]
 impl InsertAssetServerPlugin
 {
     fn new(asset_server: AssetServer) -> Self
@@ -118,6 +228,7 @@ impl InsertAssetServerPlugin
     }
 }
 
+#[cfg(feature = "winit")]
 impl Plugin for InsertAssetServerPlugin
 {
     fn build(&self, app: &mut App)
@@ -128,12 +239,44 @@ impl Plugin for InsertAssetServerPlugin
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Plugin that inserts a [`SwapCommandSender`] into a child app when it is built.
+///
+/// The backend inserts a sender into worlds when they are swapped in or warmed up, but with this plugin the sender
+/// is also available while the app is being set up. The sender's source is the child world.
+///
+/// Included in [`ChildDefaultPlugins`] and [`ChildWindowPlugins`]. Add it to headless child apps manually.
+pub struct ChildSwapCommandsPlugin
+{
+    pub swap_commands: SwapCommandSender,
+}
+
+impl ChildSwapCommandsPlugin
+{
+    pub fn new(world: &World) -> Self
+    {
+        Self { swap_commands: world.resource::<SwapCommandSender>().with_source(None) }
+    }
+}
+
+impl Plugin for ChildSwapCommandsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        let sender = self.swap_commands.with_source(Some(app.world().id()));
+        app.insert_resource(sender);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Plugin to use in addition to [`WindowPlugin`] for child worlds.
 ///
 /// We need to manually repair the `Focus` resource since the primary window isn't spawned by `WindowPlugin` for
 /// child worlds.
+#[cfg(feature = "winit")]
 struct ChildFocusRepairPlugin;
 
+#[cfg(feature = "winit")]
 impl Plugin for ChildFocusRepairPlugin
 {
     fn build(&self, app: &mut App)
@@ -150,16 +293,29 @@ impl Plugin for ChildFocusRepairPlugin
 
 //-------------------------------------------------------------------------------------------------------------------
 
-struct WorldSwapWindowPlugin;
+/// Plugin with the window plumbing needed by all windowed worlds.
+///
+/// Added by [`WorldSwapPlugin`] and the child plugin groups, and by [`WorldSwapApp::new`] if a windowed child app
+/// is missing it.
+#[cfg(feature = "winit")]
+pub(crate) struct WorldSwapWindowPlugin;
 
+#[cfg(feature = "winit")]
 impl Plugin for WorldSwapWindowPlugin
 {
     fn build(&self, app: &mut App)
     {
         app.init_resource::<WindowEventCache>()
+            .init_resource::<WindowEventStamps>()
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<WindowScaleFactorChanged>()
             .add_event::<WindowThemeChanged>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowMoved>()
+            .add_event::<WindowFocused>()
+            .add_event::<WindowOccluded>()
+            .add_event::<WindowCloseRequested>()
+            .add_event::<DeferredWindowCloseRequested>()
             .add_systems(Last, collect_window_events.in_set(WorldSwapSet));
     }
 }
@@ -174,6 +330,91 @@ pub struct WorldSwapSet;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Links the worldswap subapp with the app's render subapp.
+#[cfg(feature = "render")]
+fn link_render_app(app: &mut App, worldswap_subapp: &mut SubApp, swap_commands: SwapCommandSender)
+{
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else { return };
+    let target = RenderWorkerTarget::new();
+    let worker_id = target.new_worker_id();
+
+    // Render-world systems can send swap commands.
+    // - We do this while building because pipelined rendering moves the RenderApp to another thread on cleanup.
+    render_app
+        .add_plugins(RenderWorkerPlugin {
+            worker: RenderWorker { id: worker_id, target: target.clone() },
+        })
+        .insert_resource(swap_commands);
+
+    // We save the target in this world so it can be used to make new apps, and save it in the worldswap
+    // subapp to set the current render worker target.
+    app.insert_resource(target.clone());
+    app.insert_resource(worker_id);
+    worldswap_subapp.insert_resource(target);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn validate_build(plugin: &WorldSwapPlugin, app: &App) -> Result<(), WorldSwapPluginError>
+{
+    // Worlds send swap commands from the app's thread, which would deadlock on a full blocking channel.
+    if plugin.channel_bound.can_block() {
+        return Err(WorldSwapPluginError::BlockingChannelBound);
+    }
+
+    // Require app uses the `Main` schedule, in order to ensure consistency between the initial app and child
+    // apps.
+    if app.main().update_schedule != Some(Main.intern()) {
+        return Err(WorldSwapPluginError::MainScheduleNotMain);
+    }
+    if app.get_sub_app(WorldSwapSubApp).is_some() {
+        return Err(WorldSwapPluginError::DuplicatePlugin);
+    }
+
+    // Child apps are linked to a parent before WorldSwapPlugin would have a chance to link them.
+    if app.world().contains_resource::<SwapCommandSender>() {
+        return Err(WorldSwapPluginError::ChildApp);
+    }
+    #[cfg(feature = "render")]
+    if app.world().contains_resource::<RenderWorkerTarget>() {
+        return Err(WorldSwapPluginError::ChildApp);
+    }
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "render")]
+fn validate_finish(app: &App) -> Result<(), WorldSwapPluginError>
+{
+    let Some(render_app) = app.get_sub_app(RenderApp) else { return Ok(()) };
+
+    // If the RenderApp didn't exist when WorldSwapPlugin was built, then no render worker target was made.
+    if !app.world().contains_resource::<RenderWorkerTarget>() {
+        return Err(WorldSwapPluginError::AddedBeforeRenderPlugin);
+    }
+    if !render_app.world().contains_resource::<RenderInstance>() {
+        return Err(WorldSwapPluginError::MissingRenderInstance);
+    }
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "render")]
+fn validate_cleanup(app: &App) -> Result<(), WorldSwapPluginError>
+{
+    // If the bevy/bevy_render feature is enabled, then render subapps should have been consolidated.
+    if app.get_sub_app(RenderApp).is_some() && app.get_sub_app(RenderExtractApp).is_some() {
+        return Err(WorldSwapPluginError::RenderSubAppsNotConsolidated);
+    }
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// If you want to reuse the parent world's assets in the child world, then you must insert a clone of the parent
 /// world's [`AssetServer`] to the child world. This should be done before adding [`AssetPlugin`] to your app,
 /// otherwise an extra asset server will be constructed and dropped needlessly.
@@ -196,6 +437,22 @@ pub enum BackgroundTickRate
     },
     /// The background world updates in every tick that the main world updates.
     EveryTick,
+    /// The background world updates in every tick that the main world updates, advancing its time by the same
+    /// delta as the foreground world.
+    ///
+    /// The background world's fixed timestep is set to the foreground world's timestep, so both worlds will run
+    /// the same number of fixed updates each tick. This is useful for keeping a shadow/prediction world aligned
+    /// with the live world (e.g. for rollback). Both worlds should use the same [`Time<Virtual>`] settings.
+    ///
+    /// The background world does not update in ticks where a swap is applied.
+    Lockstep,
+    /// The background world only runs the given schedule instead of [`Main`], at most once per `interval`.
+    ///
+    /// Useful for keeping a paused online world connected, e.g. by running a schedule with network heartbeat and
+    /// socket-pumping systems. Since `Main` doesn't run, time and event buffers aren't updated in the background.
+    ///
+    /// Use [`BackgroundTickRate::schedule`] to make this.
+    Schedule(InternedScheduleLabel, Duration),
     // /// The background world updates at a fixed tick rate.
     // ///
     // /// The background world won't update more than once per main world tick.
@@ -204,22 +461,159 @@ pub enum BackgroundTickRate
     //todo: Custom(callback fn),
 }
 
+impl BackgroundTickRate
+{
+    /// Makes a [`BackgroundTickRate::Schedule`] tick rate.
+    pub fn schedule(label: impl ScheduleLabel, interval: Duration) -> Self
+    {
+        Self::Schedule(label.intern(), interval)
+    }
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 pub type SwapRecoveryFn = fn(&mut World, WorldSwapApp);
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Controls what happens to windows created by a world before it enters the foreground.
+///
+/// Windows belong to the foreground world, so a world that creates OS windows while suspended or in the background
+/// (e.g. during warm-up ticks) can't keep them when it enters the foreground.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum PreForegroundWindowPolicy
+{
+    /// Reject [`SwapCommand::Pass`] and [`SwapCommand::Fork`] commands with
+    /// [`SwapErrorReason::WindowsCreatedOutsideForeground`].
+    ///
+    /// Background worlds that created windows can't be rejected when swapped in, so their windows are closed and
+    /// despawned with an error log.
+    #[default]
+    Error,
+    /// Close the windows, despawn their entities, and log a warning.
+    Despawn,
+    /// Close the windows but keep their entities, so the OS windows are created again once the world is in the
+    /// foreground.
+    Defer,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Controls which [`SwapCommand`] is applied when several are received in the same tick.
+///
+/// Commands that aren't applied are handled by [`WorldSwapPlugin::discard_policy`], except with
+/// [`QueueAll`](Self::QueueAll). [`SwapCommand::Custom`] commands skip arbitration and are always applied.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum CommandArbitration
+{
+    /// Apply the last command sent by a world other than the background world, or the last background command if
+    /// there are no others.
+    #[default]
+    ForegroundWins,
+    /// Apply the last command sent by the background world, or the last command from other worlds if the
+    /// background world didn't send any.
+    BackgroundWins,
+    /// Apply the first command received.
+    FirstWins,
+    /// Apply commands in the order they are received, one per tick.
+    ///
+    /// Queued commands are applied even if the world that sent them has since been swapped out.
+    QueueAll,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Controls what happens to [`SwapCommand`]s that aren't applied because another command was selected by
+/// [`CommandArbitration`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum DiscardPolicy
+{
+    /// Log a warning and discard the command, without sending an error.
+    #[default]
+    Warn,
+    /// Discard the command without logging or sending an error.
+    Silent,
+    /// Discard the command and send a [`SwapError`] with [`SwapErrorReason::Superseded`] to the world that sent
+    /// it, without logging.
+    ErrorEvent,
+    /// Keep the command and apply it in a later tick, as with [`CommandArbitration::QueueAll`].
+    ///
+    /// Queued commands are applied even if the world that sent them has since been swapped out.
+    Queue,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Controls which [`AppExit`] is used when [`WorldSwapPlugin::abort_on_background_exit`] shuts down the app.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum BackgroundExitCode
+{
+    /// Exit with [`AppExit::Success`].
+    #[default]
+    Success,
+    /// Exit with [`AppExit::Error`] using the given code.
+    Error(NonZeroU8),
+    /// Exit with the last [`AppExit`] sent by the background world.
+    Forward,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Controls frame pacing for the combined update loop.
+///
+/// Each app tick updates the foreground world, hands it off to the renderer, and then updates the background
+/// world. The frame time is measured from the start of the foreground update.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct WorldSwapFramePolicy
+{
+    /// Minimum duration of each app tick.
+    ///
+    /// If a tick finishes early, the backend sleeps until the frame time has elapsed. `None` means the loop rate
+    /// is uncapped.
+    ///
+    /// `None` by default.
+    pub min_frame_time: Option<Duration>,
+    /// Frame time that must remain after the foreground update and render hand-off for the background world to
+    /// update.
+    ///
+    /// Skipped background updates are not made up later. Ignored if [`Self::min_frame_time`] is `None`.
+    ///
+    /// Zero by default.
+    pub background_headroom: Duration,
+}
+
+impl WorldSwapFramePolicy
+{
+    /// Makes a policy that caps the loop at `rate` ticks per second.
+    pub fn max_rate(rate: f64) -> Self
+    {
+        Self { min_frame_time: Some(Duration::from_secs_f64(1.0 / rate)), ..default() }
+    }
+
+    /// Sets [`Self::background_headroom`].
+    pub fn with_background_headroom(mut self, headroom: Duration) -> Self
+    {
+        self.background_headroom = headroom;
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Sets up world swapping for an [`App`].
 ///
 /// Don't use this for setting up secondary apps. There are two types of secondary apps, headless and windowed.
 /// - **Headless**: No extra plugin is required. If your secondary app will load assets, clone the parent's
-/// [`AssetServer`] resource into the app (insert it *before* [`AssetPlugin`]).
+/// [`AssetServer`] resource into the app (insert it *before* [`AssetPlugin`]). Add [`ChildSwapCommandsPlugin`] if
+/// the app needs a [`SwapCommandSender`] before it is first swapped in.
 /// - **Windowed**: Use [`ChildDefaultPlugins`] instead of [`DefaultPlugins`].
 ///
 /// # Panics
 /// - Panics if the app's [`App::main_schedule_label`] is not [`Main`].
 /// - Panics if the `bevy/bevy_render` feature is enabled but this plugin isn't added after [`DefaultPlugins`].
+/// - Panics if added to a child app.
+///
+/// See [`WorldSwapPluginError`] for details. Use [`Self::try_add`] to detect build-time errors without panicking.
 #[derive(Resource, Clone)]
 pub struct WorldSwapPlugin
 {
@@ -245,12 +639,83 @@ pub struct WorldSwapPlugin
     /// you manually pause it. The `freeze_time` option in [`BackgroundTickRate::Never`] only applies to worlds in
     /// the background.
     pub swap_join_recovery: Option<SwapRecoveryFn>,
+    /// Callback called on the background world when a [`SwapCommand::AbortAll`] is applied.
+    ///
+    /// This allows you to save data from the background world before the app shuts down.
+    pub abort_recovery: Option<SwapRecoveryFn>,
     /// Controls whether then app should shut down when the background world exits.
     ///
     /// This does nothing on [`BackgroundTickRate::Never`].
     ///
     /// False by default.
     pub abort_on_background_exit: bool,
+    /// Controls the [`AppExit`] used when [`Self::abort_on_background_exit`] shuts down the app.
+    ///
+    /// By default, equals [`BackgroundExitCode::Success`].
+    pub background_exit_code: BackgroundExitCode,
+    /// Sub apps that should always run right after worlds are swapped.
+    ///
+    /// Bevy runs sub apps in an unspecified order, so a sub app that extracts from the main world may see the
+    /// world before or after a swap depending on the tick. This causes 1-frame hiccups when swapping. Listed sub
+    /// apps are removed from the app during [`Plugin::cleanup`] and run by the `bevy_worldswap` backend in the
+    /// listed order, after swap commands are applied and the foreground world is rendered. Like rendering, they
+    /// are not run on ticks where worlds are swapped.
+    ///
+    /// The render app is always ordered by the backend and should not be listed.
+    ///
+    /// Empty by default.
+    pub ordered_subapps: Vec<InternedAppLabel>,
+    /// Default behavior when swapping worlds.
+    ///
+    /// Can be overridden for individual commands with [`SwapCommandSender::send_with`].
+    pub swap_behavior: SwapBehavior,
+    /// Controls which command is applied when several are received in the same tick.
+    ///
+    /// By default, equals [`CommandArbitration::ForegroundWins`].
+    pub command_arbitration: CommandArbitration,
+    /// Controls what happens to commands that aren't selected by [`Self::command_arbitration`].
+    ///
+    /// By default, equals [`DiscardPolicy::Warn`].
+    pub discard_policy: DiscardPolicy,
+    /// Controls what happens to windows created by worlds before they enter the foreground.
+    ///
+    /// By default, equals [`PreForegroundWindowPolicy::Error`].
+    pub pre_foreground_windows: PreForegroundWindowPolicy,
+    /// Controls frame pacing for the combined loop of foreground update, render hand-off, and background update.
+    ///
+    /// Uncapped by default.
+    pub frame_policy: WorldSwapFramePolicy,
+    /// Enables per-world profiling of each app tick.
+    ///
+    /// The backend measures wall time spent in the foreground and background worlds' `Main` schedules and in the
+    /// foreground world's render app. See [`WorldSwapFrameTimings`].
+    ///
+    /// False by default.
+    pub profiling: bool,
+    /// Enables periodic screenshots of the foreground world. See [`FrameCapture`].
+    ///
+    /// Requires the `render` and `winit` features.
+    ///
+    /// `None` by default.
+    #[cfg(all(feature = "render", feature = "winit"))]
+    pub frame_capture: Option<FrameCapture>,
+    /// Callbacks that run before and after each stage of the backend's tick.
+    ///
+    /// This lets advanced users customize how worlds are swapped, rendered, and updated. See [`SwapStage`].
+    ///
+    /// Empty by default.
+    pub middleware: SwapMiddleware,
+    /// Capacity of the channels used for swap commands, attention requests, and background inspections.
+    ///
+    /// Use [`ChannelBound::Bounded`] to limit how many messages can pile up if the backend stalls. Channels made
+    /// by [`RpcBridge::with_bound`] and [`WorldSwapChannels::with_bound`] are configured separately.
+    ///
+    /// [`OverflowPolicy::Block`] is rejected with [`WorldSwapPluginError::BlockingChannelBound`], since worlds
+    /// send on these channels from the app's thread. Swap commands dropped by the overflow policy are reported
+    /// with a [`SwapError`] whose reason is [`SwapErrorReason::ChannelFull`].
+    ///
+    /// By default, equals [`ChannelBound::Unbounded`].
+    pub channel_bound: ChannelBound,
 }
 
 impl Default for WorldSwapPlugin
@@ -261,108 +726,202 @@ impl Default for WorldSwapPlugin
             background_tick_rate: BackgroundTickRate::Never { freeze_time: true },
             swap_pass_recovery: None,
             swap_join_recovery: None,
+            abort_recovery: None,
             abort_on_background_exit: false,
+            background_exit_code: BackgroundExitCode::Success,
+            ordered_subapps: Vec::default(),
+            swap_behavior: SwapBehavior::default(),
+            command_arbitration: CommandArbitration::ForegroundWins,
+            discard_policy: DiscardPolicy::Warn,
+            pre_foreground_windows: PreForegroundWindowPolicy::Error,
+            frame_policy: WorldSwapFramePolicy::default(),
+            profiling: false,
+            #[cfg(all(feature = "render", feature = "winit"))]
+            frame_capture: None,
+            middleware: SwapMiddleware::default(),
+            channel_bound: ChannelBound::Unbounded,
         }
     }
 }
 
+impl WorldSwapPlugin
+{
+    /// Adds this plugin to the app, returning an error instead of panicking if the app is misconfigured.
+    ///
+    /// Only errors that can be detected before the plugin is built are returned. Errors detected when the app is
+    /// finished or cleaned up still panic, since Bevy doesn't let plugins report errors from those steps.
+    ///
+    /// # Panics
+    /// These panics happen in [`App::finish`] and [`App::cleanup`], not in this method.
+    /// - Panics with [`WorldSwapPluginError::AddedBeforeRenderPlugin`] if the app's `RenderApp` was added after
+    ///   this plugin.
+    /// - Panics with [`WorldSwapPluginError::MissingRenderInstance`] if the app's `RenderApp` doesn't have a
+    ///   `RenderInstance`.
+    /// - Panics with [`WorldSwapPluginError::RenderSubAppsNotConsolidated`] if the app's render subapps weren't
+    ///   consolidated by pipelined rendering.
+    pub fn try_add(self, app: &mut App) -> Result<(), WorldSwapPluginError>
+    {
+        if app.is_plugin_added::<Self>() {
+            return Err(WorldSwapPluginError::DuplicatePlugin);
+        }
+        validate_build(&self, app)?;
+        app.add_plugins(self);
+        Ok(())
+    }
+}
+
 impl Plugin for WorldSwapPlugin
 {
     fn build(&self, app: &mut App)
     {
-        // Require app uses the `Main` schedule, in order to ensure consistency between the initial app and child
-        // apps.
-        if app.main().update_schedule != Some(Main.intern()) {
-            panic!("failed adding WorldSwapPlugin, app's main_schedule_label is not Main");
+        if let Err(err) = validate_build(self, app) {
+            panic!("failed adding WorldSwapPlugin: {err}");
         }
 
+        // Report which world was updating when panics occur.
+        install_panic_hook();
+
         // Prep worldswap subapp.
-        let (sender, receiver) = crossbeam::channel::unbounded();
+        let (sender, receiver) = self.channel_bound.channel();
+        // - Dropped commands are reported on an unbounded channel so the reports can't be dropped too.
+        let (dropped_sender, dropped_receiver) = crossbeam::channel::unbounded();
+        let (attention_sender, attention_receiver) = self.channel_bound.channel();
+        #[cfg(feature = "inspect")]
+        let (inspect_sender, inspect_receiver) = self.channel_bound.channel();
+
+        let shared_store = SharedStore::default();
+        let registry = WorldRegistry::new(app.world().id());
+        // All senders are derived from one base sender so command ids are unique across worlds.
+        let base_swap_commands = SwapCommandSender::new(sender, dropped_sender);
+        let swap_commands = base_swap_commands.with_source(Some(app.world().id()));
+        let remote_handle = WorldSwapRemoteHandle::new(swap_commands.clone(), registry.clone());
 
         let mut worldswap_subapp = SubApp::new();
         worldswap_subapp
             .insert_resource(self.clone())
-            .insert_resource(SwapCommandSender(sender.clone()))
+            .insert_resource(shared_store.clone())
+            .insert_resource(registry.clone())
+            .insert_resource(remote_handle.clone())
+            .insert_resource(base_swap_commands)
             .insert_resource(SwapCommandReceiver(receiver))
-            .insert_resource(WorldSwapSubAppState::Running);
+            .insert_resource(DroppedSwapCommandReceiver(dropped_receiver))
+            .insert_resource(AttentionSender(attention_sender.clone()))
+            .insert_resource(AttentionReceiver(attention_receiver))
+            .insert_resource(WorldSwapSubAppState::Running)
+            .init_resource::<FramePacing>()
+            .init_resource::<FrameProfiler>();
+        #[cfg(all(feature = "render", feature = "winit"))]
+        worldswap_subapp.init_resource::<FrameCaptureState>();
+        #[cfg(feature = "inspect")]
+        worldswap_subapp
+            .insert_resource(BackgroundInspector(inspect_sender.clone()))
+            .insert_resource(BackgroundInspectionReceiver(inspect_receiver));
 
         worldswap_subapp
             .world_mut()
             .insert_non_send_resource(BackgroundApp { app: None });
+        worldswap_subapp
+            .world_mut()
+            .insert_non_send_resource(QueuedSwapRequests::default());
 
         worldswap_subapp.init_schedule(Main);
         worldswap_subapp.set_extract(world_swap_extract);
 
         // Link the worldswap subapp with our render subapp.
-        let world_id = RenderWorkerId::from(app.world());
-        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
-            let target = RenderWorkerTarget::new();
-
-            render_app.add_plugins(RenderWorkerPlugin {
-                worker: RenderWorker { id: world_id, target: target.clone() },
-            });
-
-            // We save the target in this world so it can be used to make new apps, and save it in the worldswap
-            // subapp to set the current render worker target.
-            app.insert_resource(target.clone());
-            worldswap_subapp.insert_resource(target.clone());
-        }
+        #[cfg(feature = "render")]
+        link_render_app(app, &mut worldswap_subapp, swap_commands.clone());
 
         // Save the worldswap subapp.
         app.insert_sub_app(WorldSwapSubApp, worldswap_subapp);
 
         // Set up the original App's world as a world-swap child.
         // - We include `WorldSwapWindowPlugin` because we don't know yet if this app actually uses windows or not.
-        app.add_plugins(WorldSwapWindowPlugin)
-            .insert_resource(SwapCommandSender(sender))
-            .insert_resource(WorldSwapStatus::Foreground);
+        #[cfg(feature = "winit")]
+        app.add_plugins(WorldSwapWindowPlugin);
+        app.add_plugins(SharedStorePlugin::from_store(shared_store))
+            .add_plugins(PendingSwapPlugin)
+            .insert_resource(registry)
+            .insert_resource(remote_handle)
+            .insert_resource(swap_commands)
+            .insert_resource(AttentionSender(attention_sender))
+            .insert_resource(WorldSwapStatus::Foreground)
+            .init_resource::<TimeAwayFromForeground>()
+            .add_event::<SwapApplied>()
+            .add_event::<SwapError>()
+            .add_event::<StaleAppExit>()
+            .add_event::<BackgroundTickReport>()
+            .add_event::<AttentionRequest>();
+        #[cfg(feature = "inspect")]
+        app.insert_resource(BackgroundInspector(inspect_sender));
+        set_panic_context(app.world(), Main);
     }
 
+    #[cfg(feature = "render")]
     fn finish(&self, app: &mut App)
     {
+        if let Err(err) = validate_finish(app) {
+            panic!("failed finishing WorldSwapPlugin: {err}");
+        }
+
         // Finish prepping our RenderApp.
         if let Some(render_app) = app.get_sub_app(RenderApp) {
-            let render_instance = render_app
-                .world()
-                .get_resource::<RenderInstance>()
-                .expect("WorldSwapPlugin must be added **AFTER** RenderPlugin");
+            let render_instance = render_app.world().resource::<RenderInstance>().clone();
             let time_sender = render_app
                 .world()
                 .get_resource::<TimeSender>()
-                .expect("RenderPlugin is missing TimeSender");
-            let time_sender = TimeSender(time_sender.0.clone());
+                .map(|time_sender| TimeSender(time_sender.0.clone()));
 
             // Transfer RenderInstance from the RenderApp to our main app so it can be transmitted to new apps.
             // - We do this in Plugin::finish because the RenderInstance is inserted to RenderApp in this method.
-            app.insert_resource(render_instance.clone());
+            app.insert_resource(render_instance);
 
             // Transfer TimeSender to our main app so we can pass it to the ForegroundApp.
-            app.insert_resource(time_sender);
+            // - Custom render backends may not install time channels, in which case the world's time is driven by
+            //   its own clock.
+            if let Some(time_sender) = time_sender {
+                app.insert_resource(time_sender);
+            }
         }
     }
 
     fn cleanup(&self, app: &mut App)
     {
-        // Panic if bevy/bevy_render feature is enabled but render subapps haven't been consolidated.
-        if app.get_sub_app(RenderApp).is_some() && app.get_sub_app(RenderExtractApp).is_some() {
-            panic!("failed removing render subapp, WorldSwapPlugin must be added after DefaultPlugins");
+        #[cfg(feature = "render")]
+        if let Err(err) = validate_cleanup(app) {
+            panic!("failed cleaning up WorldSwapPlugin: {err}");
         }
 
         // Get the render app.
-        let maybe_render_app = app
-            .remove_sub_app(RenderApp)
-            .or_else(|| app.remove_sub_app(RenderExtractApp));
+        #[cfg(feature = "render")]
+        let maybe_render_app = remove_render_subapp(app);
         let maybe_time_sender = app.world_mut().remove_resource::<TimeSender>();
 
+        // Take sub apps that should run in a fixed order relative to world swaps.
+        let ordered_subapps = self
+            .ordered_subapps
+            .iter()
+            .filter_map(|label| {
+                let subapp = app.remove_sub_app(*label);
+                if subapp.is_none() {
+                    tracing::warn!("ordered sub app {label:?} not found in app");
+                }
+                subapp
+            })
+            .collect();
+
         // Add the current world as the foreground app in the world-swap subapp.
         let worldswap_subapp = app.sub_app_mut(WorldSwapSubApp);
 
         worldswap_subapp.world_mut().insert_non_send_resource(ForegroundApp {
+            #[cfg(feature = "render")]
             render_app: maybe_render_app,
             // The initial app gets the default background tick rate.
             background_tick_rate: Some(self.background_tick_rate),
             time_sender: maybe_time_sender,
         });
+        worldswap_subapp
+            .world_mut()
+            .insert_non_send_resource(OrderedSubApps { subapps: ordered_subapps });
     }
 }
 
@@ -372,9 +931,16 @@ impl Plugin for WorldSwapPlugin
 ///
 /// This is a wrapper around Bevy's [`DefaultPlugins`], so you can edit the plugin group in the same way.
 /// - The [`RenderPlugin`] and [`WinitPlugin`] should **not** be edited.
-/// - The [`LogPlugin`] is disabled by default because we assume it was added to your initial app.
+/// - The [`LogPlugin`] is disabled by default because we assume it was added to your initial app. Use
+///   [`Self::with_log_filter`] to keep it.
+/// - The [`AssetPlugin`] reuses the parent world's [`AssetServer`]. If you use processed assets, only configure
+///   `AssetMode::Processed` in the initial app so that app's asset processor is the only one. Child worlds
+///   consume processed assets through the shared server.
 ///
 /// Don't use this for setting up your initial app. Use [`WorldSwapPlugin`] and [`DefaultPlugins`] instead.
+///
+/// Requires the `render` and `winit` features.
+#[cfg(all(feature = "render", feature = "winit"))]
 pub struct ChildDefaultPlugins
 {
     pub asset_server: AssetServer,
@@ -386,8 +952,35 @@ pub struct ChildDefaultPlugins
     /// Option that is forwarded to [`RenderPlugin`].
     pub synchronous_pipeline_compilation: bool,
     pub target: RenderWorkerTarget,
+    /// Inserted into the child's `RenderApp` so render-world systems can send swap commands.
+    pub swap_commands: SwapCommandSender,
+    pub shared_store: SharedStore,
+    /// Makes the child create its own render device with these settings instead of using the cloned device.
+    ///
+    /// Use this to run GPU-heavy worlds on a different adapter (e.g. a background bake world on a second GPU).
+    /// Adapters can be selected with [`WgpuSettings::power_preference`] or the `WGPU_ADAPTER_NAME` environment
+    /// variable.
+    ///
+    /// A world with its own device can't present to windows created by other worlds, so it should not be swapped
+    /// into the foreground while there are windows.
+    ///
+    /// `None` by default.
+    pub own_device: Option<WgpuSettings>,
+    /// Makes the child's render app only usable for compute work. See [`ComputeOnly`].
+    ///
+    /// False by default.
+    pub compute_only: bool,
+    /// Keeps the [`LogPlugin`] enabled with this filter (see [`LogPlugin::filter`]).
+    ///
+    /// Use this when no other app installed logging (e.g. in tests, or if the child app is embedded in another
+    /// app). Logging can only be installed once per process, so the child's [`LogPlugin`] will log an error if
+    /// logging was already installed.
+    ///
+    /// `None` by default.
+    pub log_filter: Option<String>,
 }
 
+#[cfg(all(feature = "render", feature = "winit"))]
 impl ChildDefaultPlugins
 {
     pub fn new(world: &mut World) -> Self
@@ -401,15 +994,35 @@ impl ChildDefaultPlugins
             instance: world.resource::<RenderInstance>().clone(),
             synchronous_pipeline_compilation: false,
             target: world.resource::<RenderWorkerTarget>().clone(),
+            swap_commands: world.resource::<SwapCommandSender>().with_source(None),
+            shared_store: world.resource::<SharedStore>().clone(),
+            own_device: None,
+            compute_only: false,
+            log_filter: None,
         }
     }
+
+    /// Sets [`Self::own_device`].
+    pub fn with_own_device(mut self, settings: WgpuSettings) -> Self
+    {
+        self.own_device = Some(settings);
+        self
+    }
+
+    /// Sets [`Self::log_filter`].
+    pub fn with_log_filter(mut self, filter: impl Into<String>) -> Self
+    {
+        self.log_filter = Some(filter.into());
+        self
+    }
 }
 
+#[cfg(all(feature = "render", feature = "winit"))]
 impl PluginGroup for ChildDefaultPlugins
 {
     fn build(self) -> PluginGroupBuilder
     {
-        DefaultPlugins
+        let builder = DefaultPlugins
             .build()
             .set(WindowPlugin {
                 primary_window: None,
@@ -417,22 +1030,103 @@ impl PluginGroup for ChildDefaultPlugins
                 close_when_requested: true,
             })
             .set(RenderPlugin {
-                render_creation: RenderCreation::Manual(
-                    self.devices,
-                    self.queue,
-                    self.adapter_info,
-                    self.adapter,
-                    self.instance,
-                ),
+                render_creation: match self.own_device {
+                    Some(settings) => RenderCreation::Automatic(settings),
+                    None => RenderCreation::Manual(
+                        self.devices,
+                        self.queue,
+                        self.adapter_info,
+                        self.adapter,
+                        self.instance,
+                    ),
+                },
                 synchronous_pipeline_compilation: self.synchronous_pipeline_compilation,
             })
-            .add_after::<RenderPlugin, RenderPluginFollowUp>(RenderPluginFollowUp::new(self.target.clone()))
+            .add(ChildSwapCommandsPlugin { swap_commands: self.swap_commands.clone() })
+            .add_after::<RenderPlugin, RenderPluginFollowUp>(RenderPluginFollowUp::new(
+                self.target,
+                self.swap_commands,
+                self.compute_only,
+            ))
             .add_before::<AssetPlugin, InsertAssetServerPlugin>(InsertAssetServerPlugin::new(self.asset_server))
             .add(ChildFocusRepairPlugin)
+            .add(SharedStorePlugin::from_store(self.shared_store))
+            .add(PendingSwapPlugin)
             .disable::<WinitPlugin>()
             .add(WinitCorePlugin)
+            .add(WorldSwapWindowPlugin);
+
+        match self.log_filter {
+            Some(filter) => builder.set(LogPlugin { filter, ..Default::default() }),
+            None => builder.disable::<LogPlugin>(),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin group for setting up a windowed child world that doesn't use [`RenderPlugin`].
+///
+/// Use this instead of [`ChildDefaultPlugins`] for child worlds that present to windows with a CPU renderer (e.g.
+/// `softbuffer`/`pixels`-style plugins). Window handles are transferred between worlds the same way as
+/// for [`ChildDefaultPlugins`]. Since the world has no render subapp, it won't wait for other worlds' renderers
+/// before updating, so your renderer should present in the world's own schedules.
+///
+/// Contains the core Bevy plugins needed for windowing, input, and assets. Add any other plugins you need
+/// separately.
+///
+/// Don't use this for setting up your initial app. Use [`WorldSwapPlugin`] instead.
+///
+/// Requires the `winit` feature.
+#[cfg(feature = "winit")]
+pub struct ChildWindowPlugins
+{
+    pub asset_server: AssetServer,
+    pub shared_store: SharedStore,
+    pub swap_commands: SwapCommandSender,
+}
+
+#[cfg(feature = "winit")]
+impl ChildWindowPlugins
+{
+    pub fn new(world: &World) -> Self
+    {
+        Self {
+            asset_server: world.resource::<AssetServer>().clone(),
+            shared_store: world.resource::<SharedStore>().clone(),
+            swap_commands: world.resource::<SwapCommandSender>().with_source(None),
+        }
+    }
+}
+
+#[cfg(feature = "winit")]
+impl PluginGroup for ChildWindowPlugins
+{
+    fn build(self) -> PluginGroupBuilder
+    {
+        PluginGroupBuilder::start::<Self>()
+            .add(TaskPoolPlugin::default())
+            .add(TypeRegistrationPlugin)
+            .add(FrameCountPlugin)
+            .add(TimePlugin)
+            .add(TransformPlugin)
+            .add(HierarchyPlugin)
+            .add(DiagnosticsPlugin)
+            .add(InputPlugin)
+            .add(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::OnAllClosed,
+                close_when_requested: true,
+            })
+            .add(AccessibilityPlugin)
+            .add(InsertAssetServerPlugin::new(self.asset_server))
+            .add(AssetPlugin::default())
+            .add(ChildFocusRepairPlugin)
+            .add(SharedStorePlugin::from_store(self.shared_store))
+            .add(ChildSwapCommandsPlugin { swap_commands: self.swap_commands })
+            .add(PendingSwapPlugin)
+            .add(WinitCorePlugin)
             .add(WorldSwapWindowPlugin)
-            .disable::<LogPlugin>()
     }
 }
 
@@ -0,0 +1,191 @@
+use std::time::Duration;
+
+use bevy::diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+use bevy::ecs::storage::SparseSetIndex;
+use bevy::ecs::world::WorldId;
+use bevy::prelude::*;
+use bevy::utils::Instant;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn world_timing(world: &World) -> WorldTiming
+{
+    WorldTiming {
+        world: world.id(),
+        label: world.get_resource::<WorldLabel>().cloned(),
+        main: Duration::ZERO,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn diagnostic_name(timing: &WorldTiming) -> String
+{
+    match &timing.label {
+        Some(label) => label.to_string(),
+        None => format!("world{}", timing.world.sparse_set_index()),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn add_measurement(store: &mut DiagnosticsStore, path: String, value: Duration)
+{
+    let path = DiagnosticPath::new(path);
+    if store.get(&path).is_none() {
+        store.add(Diagnostic::new(path.clone()).with_suffix("ms"));
+    }
+    let Some(diagnostic) = store.get_mut(&path) else { return };
+    let value = value.as_secs_f64() * 1000.0;
+    diagnostic.add_measurement(DiagnosticMeasurement { time: Instant::now(), value });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn write_diagnostics(world: &mut World, timings: &WorldSwapFrameTimings)
+{
+    let Some(mut store) = world.get_resource_mut::<DiagnosticsStore>() else { return };
+
+    let foreground = diagnostic_name(&timings.foreground);
+    add_measurement(&mut store, format!("worldswap/{foreground}/foreground_main"), timings.foreground.main);
+    add_measurement(&mut store, format!("worldswap/{foreground}/render_extract"), timings.render_extract);
+    add_measurement(&mut store, format!("worldswap/{foreground}/render_update"), timings.render_update);
+    if let Some(background) = &timings.background {
+        let name = diagnostic_name(background);
+        add_measurement(&mut store, format!("worldswap/{name}/background_main"), background.main);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Starts profiling a tick if [`WorldSwapPlugin::profiling`] is enabled.
+///
+/// Called at the start of the backend's tick, right after the foreground world's `Main` schedule.
+pub(crate) fn begin_frame_profile(subapp_world: &mut World, main_world: &World)
+{
+    if !subapp_world.resource::<WorldSwapPlugin>().profiling {
+        return;
+    }
+
+    let mut foreground = world_timing(main_world);
+    foreground.main = subapp_world.resource::<FramePacing>().foreground_elapsed();
+    subapp_world.resource_mut::<FrameProfiler>().current = Some(WorldSwapFrameTimings {
+        foreground,
+        render_extract: Duration::ZERO,
+        render_update: Duration::ZERO,
+        background: None,
+    });
+}
+
+/// Records time spent in a stage of the current tick.
+pub(crate) fn record_profile_stage(subapp_world: &mut World, stage: ProfileStage, elapsed: Duration)
+{
+    let mut profiler = subapp_world.resource_mut::<FrameProfiler>();
+    let Some(timings) = profiler.current.as_mut() else { return };
+    match stage {
+        ProfileStage::RenderExtract => timings.render_extract += elapsed,
+        ProfileStage::RenderUpdate => timings.render_update += elapsed,
+        ProfileStage::BackgroundMain => {
+            if let Some(background) = timings.background.as_mut() {
+                background.main += elapsed;
+            }
+        }
+    }
+}
+
+/// Records the background world that will update this tick.
+pub(crate) fn record_profile_background(subapp_world: &mut World)
+{
+    let Some(background) = subapp_world
+        .non_send_resource::<BackgroundApp>()
+        .app
+        .as_ref()
+        .map(|app| world_timing(&app.world))
+    else {
+        return;
+    };
+    let mut profiler = subapp_world.resource_mut::<FrameProfiler>();
+    let Some(timings) = profiler.current.as_mut() else { return };
+    timings.background = Some(background);
+}
+
+/// Reports the current tick's timings to tracing, the foreground world's [`DiagnosticsStore`], and the
+/// [`WorldSwapFrameTimings`] resource in the foreground world.
+pub(crate) fn finish_frame_profile(subapp_world: &mut World, main_world: &mut World)
+{
+    let Some(timings) = subapp_world.resource_mut::<FrameProfiler>().current.take() else { return };
+
+    let foreground = WorldName { id: timings.foreground.world, label: timings.foreground.label.as_ref() };
+    tracing::trace!("frame timings: {:?} main {:?}, render extract {:?}, render update {:?}",
+        foreground, timings.foreground.main, timings.render_extract, timings.render_update);
+    if let Some(background) = &timings.background {
+        let name = WorldName { id: background.world, label: background.label.as_ref() };
+        tracing::trace!("frame timings: {:?} background main {:?}", name, background.main);
+    }
+
+    write_diagnostics(main_world, &timings);
+    main_world.insert_resource(timings);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stages of a tick measured by the profiler.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(not(feature = "render"), allow(dead_code))]
+pub(crate) enum ProfileStage
+{
+    RenderExtract,
+    RenderUpdate,
+    BackgroundMain,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Timings collected while profiling the current tick.
+#[derive(Resource, Default)]
+pub(crate) struct FrameProfiler
+{
+    current: Option<WorldSwapFrameTimings>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wall time spent in a world's `Main` schedule.
+#[derive(Debug, Clone)]
+pub struct WorldTiming
+{
+    /// The world's id.
+    pub world: WorldId,
+    /// The world's [`WorldLabel`], if it has one.
+    pub label: Option<WorldLabel>,
+    /// Wall time spent running the world's `Main` schedule.
+    pub main: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource with wall-time measurements of the previous app tick.
+///
+/// Inserted into the foreground world each tick when [`WorldSwapPlugin::profiling`] is enabled. Measurements are
+/// also logged at `trace` level and added to the foreground world's [`DiagnosticsStore`] if it has one, under
+/// `worldswap/<world label>/<stage>`.
+#[derive(Resource, Debug, Clone)]
+pub struct WorldSwapFrameTimings
+{
+    /// Time spent in the foreground world's `Main` schedule.
+    ///
+    /// This includes other sub apps that Bevy ran before the `bevy_worldswap` backend.
+    pub foreground: WorldTiming,
+    /// Time spent extracting the foreground world into its render app.
+    pub render_extract: Duration,
+    /// Time spent updating the render app.
+    ///
+    /// With pipelined rendering this only measures handing the frame off to the render thread.
+    pub render_update: Duration,
+    /// Time spent in the background world's `Main` schedule, if there is a background world.
+    pub background: Option<WorldTiming>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,301 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+
+use bevy::ecs::world::WorldId;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Updates the [`WorldRegistry`] on the first tick and after swaps, and mirrors it into the foreground and
+/// background worlds.
+pub(crate) fn sync_world_registry(subapp_world: &mut World, main_world: &mut World)
+{
+    let background_world = subapp_world.non_send_resource::<BackgroundApp>().app.as_ref().map(|app| &app.world);
+    let background = background_world.map(|world| world.id());
+    let labels: Vec<_> = [Some(&*main_world), background_world]
+        .into_iter()
+        .flatten()
+        .filter_map(|world| Some((world.id(), world.get_resource::<WorldLabel>()?.clone())))
+        .collect();
+    let seeds: Vec<_> = [Some(&*main_world), background_world]
+        .into_iter()
+        .flatten()
+        .filter_map(|world| Some((world.id(), world.get_resource::<WorldSeed>()?.0)))
+        .collect();
+
+    #[cfg(feature = "render")]
+    let target_id = subapp_world.get_resource::<RenderWorkerTarget>().map(|target| target.id());
+
+    let mut registry = subapp_world.resource_mut::<WorldRegistry>();
+    registry.update(main_world.id(), background, labels, seeds);
+    #[cfg(feature = "render")]
+    registry.record_render_worker(main_world);
+
+    // Forget render workers of dropped worlds, unless they are still running.
+    #[cfg(feature = "render")]
+    {
+        let (foreground, background) = (registry.foreground, registry.background);
+        registry.render_workers.retain(|id, world| {
+            *world == foreground || Some(*world) == background || Some(*id) == target_id
+        });
+    }
+
+    let registry = registry.clone();
+
+    if let Some(background_app) = subapp_world.non_send_resource_mut::<BackgroundApp>().app.as_mut() {
+        background_app.world.insert_resource(registry.clone());
+    }
+    main_world.insert_resource(registry);
+
+    sync_remote_registry(subapp_world);
+    subapp_world.insert_resource(WorldRegistrySynced);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Inserted in the backend's world once the [`WorldRegistry`] has been synced with the worlds.
+#[derive(Resource)]
+pub(crate) struct WorldRegistrySynced;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Formats a world as its [`WorldLabel`] if it has one, otherwise as its [`WorldId`].
+pub(crate) struct WorldName<'a>
+{
+    pub(crate) id: WorldId,
+    pub(crate) label: Option<&'a WorldLabel>,
+}
+
+impl<'a> WorldName<'a>
+{
+    pub(crate) fn of(world: &'a World) -> Self
+    {
+        Self { id: world.id(), label: world.get_resource::<WorldLabel>() }
+    }
+}
+
+impl Debug for WorldName<'_>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        match self.label {
+            Some(label) => write!(f, "world {:?}", label.as_str()),
+            None => write!(f, "{:?}", self.id),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource with a human-readable name for a world.
+///
+/// Labels are shown instead of [`WorldId`]s in logs, the [`WorldRegistry`], and [`WorldPanicInfo`]. Child worlds
+/// can be labeled with [`WorldSwapApp::new_labeled`]. The initial world can be labeled by inserting this resource.
+#[derive(Resource, Debug, Clone, Eq, PartialEq, Hash)]
+pub struct WorldLabel(Arc<str>);
+
+impl WorldLabel
+{
+    /// Makes a new label.
+    pub fn new(label: impl Into<Arc<str>>) -> Self
+    {
+        Self(label.into())
+    }
+
+    /// Gets the label as a string.
+    pub fn as_str(&self) -> &str
+    {
+        &self.0
+    }
+}
+
+impl Display for WorldLabel
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for WorldLabel
+{
+    fn from(label: &str) -> Self
+    {
+        Self::new(label)
+    }
+}
+
+impl From<String> for WorldLabel
+{
+    fn from(label: String) -> Self
+    {
+        Self::new(label)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that tracks the live worlds and which world introduced each of them.
+///
+/// A world's parent is the world that was in the foreground when it was added with
+/// [`SwapCommand::Pass`] or [`SwapCommand::Fork`]. The parent may have been dropped since then.
+///
+/// Since [`SwapCommand::Join`] always returns to the background world, a world can check whether joining will
+/// return to its parent with [`WorldRegistry::background_is_parent_of`].
+///
+/// The registry is kept up to date in the foreground and background worlds.
+#[derive(Resource, Debug, Clone)]
+pub struct WorldRegistry
+{
+    foreground: WorldId,
+    background: Option<WorldId>,
+    parents: HashMap<WorldId, WorldId>,
+    labels: HashMap<WorldId, WorldLabel>,
+    seeds: HashMap<WorldId, u64>,
+    #[cfg(feature = "render")]
+    render_workers: HashMap<RenderWorkerId, WorldId>,
+}
+
+impl WorldRegistry
+{
+    pub(crate) fn new(foreground: WorldId) -> Self
+    {
+        Self {
+            foreground,
+            background: None,
+            parents: HashMap::default(),
+            labels: HashMap::default(),
+            seeds: HashMap::default(),
+            #[cfg(feature = "render")]
+            render_workers: HashMap::default(),
+        }
+    }
+
+    /// Records a world's [`RenderWorkerId`] if it has one.
+    #[cfg(feature = "render")]
+    pub(crate) fn record_render_worker(&mut self, world: &World)
+    {
+        let Some(id) = world.get_resource::<RenderWorkerId>() else { return };
+        self.render_workers.insert(*id, world.id());
+    }
+
+    /// Gets the foreground world.
+    pub fn foreground(&self) -> WorldId
+    {
+        self.foreground
+    }
+
+    /// Gets the background world.
+    pub fn background(&self) -> Option<WorldId>
+    {
+        self.background
+    }
+
+    /// Returns `true` if the world is in the foreground or background.
+    pub fn is_live(&self, world: WorldId) -> bool
+    {
+        self.foreground == world || self.background == Some(world)
+    }
+
+    /// Gets the [`WorldLabel`] of a live world, if it has one.
+    pub fn label(&self, world: WorldId) -> Option<&WorldLabel>
+    {
+        self.labels.get(&world)
+    }
+
+    /// Gets the [`WorldSeed`] of a live world, if it has one.
+    pub fn seed(&self, world: WorldId) -> Option<u64>
+    {
+        self.seeds.get(&world).copied()
+    }
+
+    /// Finds a live world by its [`WorldLabel`].
+    pub fn find(&self, label: &str) -> Option<WorldId>
+    {
+        self.labels
+            .iter()
+            .find(|(_, world_label)| world_label.as_str() == label)
+            .map(|(world, _)| *world)
+    }
+
+    pub(crate) fn name(&self, world: WorldId) -> WorldName<'_>
+    {
+        WorldName { id: world, label: self.label(world) }
+    }
+
+    /// Gets the parent of a live world.
+    ///
+    /// Returns `None` for the initial world.
+    pub fn parent(&self, world: WorldId) -> Option<WorldId>
+    {
+        self.parents.get(&world).copied()
+    }
+
+    /// Iterates live worlds whose parent is `world`.
+    pub fn children(&self, world: WorldId) -> impl Iterator<Item = WorldId> + '_
+    {
+        self.parents
+            .iter()
+            .filter(move |(_, parent)| **parent == world)
+            .map(|(child, _)| *child)
+    }
+
+    /// Resolves a [`RenderWorkerId`] to its world and that world's status.
+    ///
+    /// The status is `None` if the world was dropped or passed to a recovery callback (its renderer may still be
+    /// finishing a job). Returns `None` if the worker is unknown.
+    ///
+    /// Requires the `render` feature.
+    #[cfg(feature = "render")]
+    pub fn resolve_render_worker(&self, id: RenderWorkerId) -> Option<(WorldId, Option<WorldSwapStatus>)>
+    {
+        let world = *self.render_workers.get(&id)?;
+        let status = if world == self.foreground {
+            Some(WorldSwapStatus::Foreground)
+        } else if Some(world) == self.background {
+            Some(WorldSwapStatus::Background)
+        } else {
+            None
+        };
+        Some((world, status))
+    }
+
+    /// Returns `true` if the background world is the parent of `world`.
+    pub fn background_is_parent_of(&self, world: WorldId) -> bool
+    {
+        self.background.is_some() && self.background == self.parent(world)
+    }
+
+    fn update(
+        &mut self,
+        foreground: WorldId,
+        background: Option<WorldId>,
+        labels: Vec<(WorldId, WorldLabel)>,
+        seeds: Vec<(WorldId, u64)>,
+    )
+    {
+        // New worlds are children of the previous foreground world.
+        let prev_foreground = self.foreground;
+        for world in [Some(foreground), background].into_iter().flatten() {
+            if !self.is_live(world) {
+                self.parents.insert(world, prev_foreground);
+            }
+        }
+
+        self.foreground = foreground;
+        self.background = background;
+
+        // Forget dropped worlds.
+        let (foreground, background) = (self.foreground, self.background);
+        self.parents.retain(|world, _| *world == foreground || Some(*world) == background);
+        self.labels.clear();
+        self.labels.extend(labels);
+        self.seeds.clear();
+        self.seeds.extend(seeds);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
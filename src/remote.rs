@@ -0,0 +1,71 @@
+use std::sync::{Arc, RwLock};
+
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Updates the registry snapshot shared with [`WorldSwapRemoteHandles`](WorldSwapRemoteHandle).
+pub(crate) fn sync_remote_registry(subapp_world: &World)
+{
+    let registry = subapp_world.resource::<WorldRegistry>().clone();
+    let handle = subapp_world.resource::<WorldSwapRemoteHandle>();
+    let Ok(mut remote_registry) = handle.registry.write() else { return };
+    *remote_registry = registry;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for controlling world swaps from outside the app's worlds.
+///
+/// The handle is cloneable and `Send`, so it can be moved to other threads (e.g. an editor or debug UI thread, or
+/// a bridge that forwards commands from a sidecar process over IPC).
+///
+/// Commands sent with this handle have no source world, so their [`SwapApplied`] and [`SwapError`] events are
+/// sent to the foreground world.
+///
+/// Get the handle from the initial app after adding [`WorldSwapPlugin`]. It is also available in all child worlds.
+#[derive(Resource, Clone)]
+pub struct WorldSwapRemoteHandle
+{
+    commands: SwapCommandSender,
+    registry: Arc<RwLock<WorldRegistry>>,
+}
+
+impl WorldSwapRemoteHandle
+{
+    pub(crate) fn new(commands: SwapCommandSender, registry: WorldRegistry) -> Self
+    {
+        Self { commands: commands.with_source(None), registry: Arc::new(RwLock::new(registry)) }
+    }
+
+    /// Sends a [`SwapCommand`] to the `bevy_worldswap` backend.
+    ///
+    /// See [`SwapCommandSender::send`].
+    pub fn send(&self, command: SwapCommand) -> SwapCommandId
+    {
+        self.commands.send(command)
+    }
+
+    /// Sends a [`SwapCommand`] to the `bevy_worldswap` backend with [`SwapOptions`].
+    ///
+    /// See [`SwapCommandSender::send_with`].
+    pub fn send_with(&self, command: SwapCommand, options: SwapOptions) -> SwapCommandId
+    {
+        self.commands.send_with(command, options)
+    }
+
+    /// Gets a snapshot of the [`WorldRegistry`].
+    ///
+    /// The snapshot is updated by the backend after each swap.
+    pub fn registry(&self) -> WorldRegistry
+    {
+        match self.registry.read() {
+            Ok(registry) => registry.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
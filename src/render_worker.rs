@@ -1,9 +1,10 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
-use bevy::ecs::storage::SparseSetIndex;
+use bevy::app::{AppLabel, InternedAppLabel, SubApp};
 use bevy::prelude::*;
-use bevy::render::{Render, RenderSet};
+use bevy::render::pipelined_rendering::RenderExtractApp;
+use bevy::render::{Render, RenderApp, RenderSet};
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -23,7 +24,38 @@ fn unset_render_worker(worker: Res<RenderWorker>)
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Debug, Copy, Clone, Deref, Eq, PartialEq)]
+/// Removes the app's render subapp using the app's [`RenderSubAppLabels`].
+///
+/// The subapp's label is saved in the subapp's world so it can be re-inserted into an app later.
+pub(crate) fn remove_render_subapp(app: &mut App) -> Option<SubApp>
+{
+    let labels = app
+        .world()
+        .get_resource::<RenderSubAppLabels>()
+        .cloned()
+        .unwrap_or_default();
+    labels.0.iter().find_map(|label| {
+        let mut subapp = app.remove_sub_app(*label)?;
+        subapp.insert_resource(RenderSubAppLabel(*label));
+        Some(subapp)
+    })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The label a render subapp had in its original app.
+#[derive(Resource, Copy, Clone)]
+pub(crate) struct RenderSubAppLabel(pub(crate) InternedAppLabel);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Identifies the render worker of a world.
+///
+/// Ids are issued by [`RenderWorkerTarget`] in increasing order, so they are never reused by worlds created later.
+/// The default id means 'no render worker'.
+///
+/// Worlds with a render worker store their id as a resource.
+#[derive(Resource, Debug, Copy, Clone, Deref, Eq, PartialEq, Hash)]
 pub struct RenderWorkerId(pub(crate) usize);
 
 impl Default for RenderWorkerId
@@ -34,27 +66,29 @@ impl Default for RenderWorkerId
     }
 }
 
-impl From<&World> for RenderWorkerId
-{
-    fn from(world: &World) -> Self
-    {
-        Self(world.id().sparse_set_index())
-    }
-}
-
 //-------------------------------------------------------------------------------------------------------------------
 
 #[derive(Resource, Clone)]
 pub struct RenderWorkerTarget
 {
     worker: Arc<AtomicUsize>,
+    next_id: Arc<AtomicUsize>,
 }
 
 impl RenderWorkerTarget
 {
     pub(crate) fn new() -> Self
     {
-        Self { worker: Arc::new(AtomicUsize::new(usize::MAX)) }
+        Self {
+            worker: Arc::new(AtomicUsize::new(usize::MAX)),
+            next_id: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Issues a new unique [`RenderWorkerId`].
+    pub(crate) fn new_worker_id(&self) -> RenderWorkerId
+    {
+        RenderWorkerId(self.next_id.fetch_add(1, Ordering::Relaxed))
     }
 
     pub fn id(&self) -> RenderWorkerId
@@ -62,6 +96,12 @@ impl RenderWorkerTarget
         RenderWorkerId(self.worker.load(Ordering::Relaxed))
     }
 
+    /// Returns `true` if both targets point to the same render worker slot.
+    pub(crate) fn is_same(&self, other: &Self) -> bool
+    {
+        Arc::ptr_eq(&self.worker, &other.worker)
+    }
+
     pub(crate) fn set(&self, id: RenderWorkerId)
     {
         self.worker.store(*id, Ordering::Relaxed);
@@ -114,3 +154,97 @@ impl Plugin for RenderWorkerPlugin
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that lists the labels of render subapps managed by `bevy_worldswap`.
+///
+/// When an app is converted to a [`WorldSwapApp`](crate::WorldSwapApp), or when
+/// [`WorldSwapPlugin`](crate::WorldSwapPlugin) is cleaned up, the first subapp found with one of these labels is
+/// removed from the app and driven by the world-swap backend while the world is in the foreground. Other subapps
+/// are discarded.
+///
+/// By default contains [`RenderApp`] and [`RenderExtractApp`]. Insert this resource into your app if you use a
+/// custom render subapp.
+#[derive(Resource, Clone)]
+pub struct RenderSubAppLabels(pub Vec<InternedAppLabel>);
+
+impl RenderSubAppLabels
+{
+    /// Adds a label to the front of the list.
+    pub fn with(mut self, label: impl AppLabel) -> Self
+    {
+        self.0.insert(0, label.intern());
+        self
+    }
+}
+
+impl Default for RenderSubAppLabels
+{
+    fn default() -> Self
+    {
+        Self(vec![RenderApp.intern(), RenderExtractApp.intern()])
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that marks a world whose render subapp is only used for compute work.
+///
+/// Compute-only worlds don't coordinate with the [`RenderWorkerTarget`], so they never make other worlds wait for
+/// their renderer. Their render subapp is run after each background update (and each foreground update), so GPU
+/// work can continue in the background.
+///
+/// Compute-only worlds should not have cameras that render to windows. Add it with
+/// [`ChildDefaultPlugins::compute_only`](crate::ChildDefaultPlugins::compute_only).
+#[derive(Resource, Debug, Copy, Clone, Default)]
+pub struct ComputeOnly;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that stops the `bevy_worldswap` backend from extracting and rendering a world while it is in the
+/// foreground.
+///
+/// Windows stay alive but aren't redrawn, so they keep showing the last presented frame. Insert this while a world
+/// is purely logic-driven to save GPU power, and remove it to resume rendering. The resource travels with the
+/// world when it is swapped.
+///
+/// See [`WorldSwapApp::with_rendering_disabled`](crate::WorldSwapApp::with_rendering_disabled).
+#[derive(Resource, Debug, Copy, Clone, Default)]
+pub struct RenderingDisabled;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that reports the state of the foreground world's renderer.
+///
+/// Updated in the foreground world by the `bevy_worldswap` backend every tick, after rendering. Use it to show
+/// 'renderer busy' states or to avoid queuing render work while frames aren't being drawn (e.g. during swaps).
+///
+/// Only updated while the world is in the foreground.
+#[derive(Resource, Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct RenderHealth
+{
+    /// `true` if the world has a render subapp.
+    pub has_render_app: bool,
+    /// `true` if the world was extracted into its render subapp in the last tick.
+    ///
+    /// Worlds aren't extracted on ticks where they are swapped in, or if they have [`RenderingDisabled`].
+    pub extracted: bool,
+    /// The render worker of another world that is still running and blocking this world's renderer.
+    ///
+    /// Use [`WorldRegistry::resolve_render_worker`](crate::WorldRegistry::resolve_render_worker) to find the
+    /// world.
+    pub blocked_by: Option<RenderWorkerId>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Callback for extracting a world into its render subapp and running the render subapp.
+pub type RenderExtractFn = fn(&mut SubApp, &mut World);
+
+/// Resource that overrides how a world is extracted into its render subapp.
+///
+/// By default, the backend calls [`SubApp::extract`] and then [`SubApp::update`]. Insert this resource into a
+/// world if its render subapp needs a different sequence. The adapter travels with the world when it is swapped.
+#[derive(Resource, Copy, Clone)]
+pub struct RenderExtractAdapter(pub RenderExtractFn);
+
+//-------------------------------------------------------------------------------------------------------------------
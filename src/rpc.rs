@@ -0,0 +1,189 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn receive_requests<T: Send + Sync + 'static, R: Send + Sync + 'static>(
+    bridge: Res<RpcBridge<T, R>>,
+    mut requests: EventWriter<Request<T>>,
+)
+{
+    while let Ok(request) = bridge.request_receiver.try_recv() {
+        requests.send(request);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn receive_responses<T: Send + Sync + 'static, R: Send + Sync + 'static>(
+    bridge: Res<RpcBridge<T, R>>,
+    mut responses: EventWriter<Response<R>>,
+)
+{
+    while let Ok(response) = bridge.response_receiver.try_recv() {
+        responses.send(response);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn has_requests<T: Send + Sync + 'static, R: Send + Sync + 'static>(world: &World) -> bool
+{
+    world
+        .get_resource::<RpcBridge<T, R>>()
+        .is_some_and(|bridge| !bridge.request_receiver.is_empty())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Identifies a request sent with [`RpcBridge::request`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RpcRequestId(u64);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event emitted in the background world when a request is received from the foreground world.
+///
+/// Reply with [`RpcBridge::respond`].
+#[derive(Event, Debug, Clone)]
+pub struct Request<T>
+{
+    pub id: RpcRequestId,
+    pub value: T,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event emitted in the foreground world when a response is received from the background world.
+#[derive(Event, Debug, Clone)]
+pub struct Response<R>
+{
+    pub id: RpcRequestId,
+    pub value: R,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for sending typed requests from the foreground world to the background world, and receiving responses.
+///
+/// Requests are received as [`Request<T>`] events in the background world at the start of its next update.
+/// Responses are received as [`Response<R>`] events in the foreground world at the start of its next update.
+/// Requests sent while there is no world in the background will wait until a world with the same bridge is put in
+/// the background.
+///
+/// Use [`RpcPlugin`] to add the bridge to both worlds.
+#[derive(Resource)]
+pub struct RpcBridge<T, R>
+{
+    next_id: Arc<AtomicU64>,
+    request_sender: BoundedSender<Request<T>>,
+    request_receiver: BoundedReceiver<Request<T>>,
+    response_sender: BoundedSender<Response<R>>,
+    response_receiver: BoundedReceiver<Response<R>>,
+}
+
+impl<T, R> RpcBridge<T, R>
+{
+    /// Makes a new bridge.
+    pub fn new() -> Self
+    {
+        Self::with_bound(ChannelBound::Unbounded)
+    }
+
+    /// Makes a new bridge whose request and response channels have the given [`ChannelBound`].
+    pub fn with_bound(bound: ChannelBound) -> Self
+    {
+        let (request_sender, request_receiver) = bound.channel();
+        let (response_sender, response_receiver) = bound.channel();
+        Self {
+            next_id: Arc::new(AtomicU64::new(0)),
+            request_sender,
+            request_receiver,
+            response_sender,
+            response_receiver,
+        }
+    }
+
+    /// Sends a request to the background world.
+    pub fn request(&self, value: T) -> RpcRequestId
+    {
+        let id = RpcRequestId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        // Ignore errors.
+        let _ = self.request_sender.send(Request { id, value });
+        id
+    }
+
+    /// Sends a response to the foreground world.
+    pub fn respond(&self, id: RpcRequestId, value: R)
+    {
+        // Ignore errors.
+        let _ = self.response_sender.send(Response { id, value });
+    }
+}
+
+impl<T, R> Default for RpcBridge<T, R>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<T, R> Clone for RpcBridge<T, R>
+{
+    fn clone(&self) -> Self
+    {
+        Self {
+            next_id: self.next_id.clone(),
+            request_sender: self.request_sender.clone(),
+            request_receiver: self.request_receiver.clone(),
+            response_sender: self.response_sender.clone(),
+            response_receiver: self.response_receiver.clone(),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin for adding an [`RpcBridge`] to an app.
+///
+/// Add this plugin to each app that should share the bridge, using clones of the same bridge. Requests are only
+/// read while a world is [`in_background`], and responses are only read while a world is [`in_foreground`].
+pub struct RpcPlugin<T, R>
+{
+    bridge: RpcBridge<T, R>,
+}
+
+impl<T, R> RpcPlugin<T, R>
+{
+    pub fn new(bridge: RpcBridge<T, R>) -> Self
+    {
+        Self { bridge }
+    }
+}
+
+impl<T: Send + Sync + 'static, R: Send + Sync + 'static> Plugin for RpcPlugin<T, R>
+{
+    fn build(&self, app: &mut App)
+    {
+        app.insert_resource(self.bridge.clone())
+            .add_event::<Request<T>>()
+            .add_event::<Response<R>>()
+            .add_systems(
+                First,
+                (
+                    receive_requests::<T, R>.run_if(in_background),
+                    receive_responses::<T, R>.run_if(in_foreground),
+                ),
+            );
+
+        // Wake the world from idle parking when requests arrive.
+        register_idle_wake_check(app, has_requests::<T, R>);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
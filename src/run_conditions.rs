@@ -1,4 +1,7 @@
+use bevy::ecs::schedule::SystemConfigs;
+use bevy::ecs::world::WorldId;
 use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowRef};
 
 use crate::*;
 
@@ -73,3 +76,90 @@ pub fn entered_foreground(mut prev: Local<Option<WorldSwapStatus>>, status: Res<
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Run condition that returns `true` if [`WorldSwapStatus`] equals [`Foreground`](WorldSwapStatus::Foreground)
+/// and one of the world's windows has OS focus.
+///
+/// Window focus is kept in sync by the `bevy_worldswap` backend when windows are transferred between worlds.
+pub fn in_foreground_and_focused(status: Res<WorldSwapStatus>, windows: Query<&Window>) -> bool
+{
+    *status == WorldSwapStatus::Foreground && windows.iter().any(|window| window.focused)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returns a run condition that returns `true` if [`WorldSwapStatus`] equals
+/// [`Foreground`](WorldSwapStatus::Foreground) and the given window has OS focus.
+pub fn in_foreground_window(
+    window: WindowRef,
+) -> impl FnMut(Res<WorldSwapStatus>, Query<&Window>, Query<Entity, With<PrimaryWindow>>) -> bool + Clone
+{
+    move |status: Res<WorldSwapStatus>, windows: Query<&Window>, primary: Query<Entity, With<PrimaryWindow>>| {
+        if *status != WorldSwapStatus::Foreground {
+            return false;
+        }
+        let Some(window) = window.normalize(primary.get_single().ok()) else { return false };
+        windows.get(window.entity()).map(|window| window.focused).unwrap_or(false)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returns a run condition that returns `true` if the world's [`WorldLabel`] equals `label`.
+///
+/// Useful for plugins that are added to several worlds but should behave differently in some of them.
+pub fn is_world(label: impl Into<WorldLabel>) -> impl FnMut(Option<Res<WorldLabel>>) -> bool + Clone
+{
+    let label = label.into();
+    move |world_label: Option<Res<WorldLabel>>| world_label.is_some_and(|world_label| *world_label == label)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returns a run condition that returns `true` if the world's parent in the [`WorldRegistry`] has the given
+/// [`WorldLabel`].
+///
+/// Labels are only recorded for live worlds, so this returns `false` if the parent was dropped. The registry is
+/// updated after each swap, so this returns `false` before the world first enters the foreground or background.
+pub fn parent_is(label: impl Into<WorldLabel>) -> impl FnMut(WorldId, Option<Res<WorldRegistry>>) -> bool + Clone
+{
+    let label = label.into();
+    move |world: WorldId, registry: Option<Res<WorldRegistry>>| {
+        let Some(registry) = registry else { return false };
+        let Some(parent) = registry.parent(world) else { return false };
+        registry.label(parent) == Some(&label)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Run condition that returns `false` if the world is in the foreground and a [`SwapCommand`] will be applied
+/// after the world's current update.
+///
+/// Swaps are applied after the foreground world's `Main` schedule, and may transfer windows out of the world or
+/// add new ones. Use this to protect systems that must not run right before their world's window state is
+/// changed. Commands sent after the system runs in the same tick are not detected.
+///
+/// See [`swap_safe`].
+pub fn no_swap_pending(world: &World) -> bool
+{
+    if world.get_resource::<WorldSwapStatus>() != Some(&WorldSwapStatus::Foreground) {
+        return true;
+    }
+    if world.contains_resource::<QueuedSwapMarker>() {
+        return false;
+    }
+    !world.get_resource::<SwapCommandSender>().is_some_and(|sender| sender.has_pending())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Adds the [`no_swap_pending`] run condition to systems.
+///
+/// Useful for exclusive systems that must not run in the same tick as a swap is applied to their world.
+pub fn swap_safe<M>(systems: impl IntoSystemConfigs<M>) -> SystemConfigs
+{
+    systems.run_if(no_swap_pending)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
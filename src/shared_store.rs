@@ -0,0 +1,199 @@
+use std::any::{Any, TypeId};
+use std::sync::{Arc, RwLock};
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Emits [`SharedStoreChanged`] events for values that changed since this system last ran.
+fn detect_shared_store_changes(
+    mut seen: Local<HashMap<TypeId, u64>>,
+    store: Res<SharedStore>,
+    mut changed: EventWriter<SharedStoreChanged>,
+)
+{
+    let Ok(inner) = store.inner.read() else { return };
+
+    // Inserted or modified values.
+    for (type_id, (version, _)) in inner.values.iter() {
+        if seen.get(type_id) == Some(version) {
+            continue;
+        }
+        seen.insert(*type_id, *version);
+        changed.send(SharedStoreChanged { type_id: *type_id });
+    }
+
+    // Removed values.
+    seen.retain(|type_id, _| {
+        if inner.values.contains_key(type_id) {
+            return true;
+        }
+        changed.send(SharedStoreChanged { type_id: *type_id });
+        false
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Default)]
+struct SharedStoreInner
+{
+    next_version: u64,
+    values: HashMap<TypeId, (u64, Box<dyn Any + Send + Sync>)>,
+}
+
+impl SharedStoreInner
+{
+    fn bump(&mut self) -> u64
+    {
+        self.next_version += 1;
+        self.next_version
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event sent in a world when a value in the [`SharedStore`] was inserted, modified, or removed.
+///
+/// Changes are detected at the start of each world update, so a world in the background will only see changes
+/// when it updates.
+#[derive(Event, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SharedStoreChanged
+{
+    /// The type of the value that changed.
+    pub type_id: TypeId,
+}
+
+impl SharedStoreChanged
+{
+    /// Returns `true` if the changed value has type `T`.
+    pub fn is<T: 'static>(&self) -> bool
+    {
+        self.type_id == TypeId::of::<T>()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource for storing values that are visible in every world (e.g. player profile, feature flags).
+///
+/// Values are keyed by type. All clones of the store share the same values.
+///
+/// The store is inserted into the initial app by [`WorldSwapPlugin`](crate::WorldSwapPlugin), and into child apps
+/// by [`ChildDefaultPlugins`](crate::ChildDefaultPlugins) and [`ChildWindowPlugins`](crate::ChildWindowPlugins).
+/// Headless child apps can add [`SharedStorePlugin`]. Worlds that don't have a store when they enter the
+/// foreground will receive one, but won't get [`SharedStoreChanged`] events without the plugin.
+#[derive(Resource, Clone, Default)]
+pub struct SharedStore
+{
+    inner: Arc<RwLock<SharedStoreInner>>,
+}
+
+impl SharedStore
+{
+    /// Gets the store's version, which changes whenever a value is inserted, modified, or removed.
+    pub(crate) fn version(&self) -> u64
+    {
+        self.inner.read().map(|inner| inner.next_version).unwrap_or_default()
+    }
+
+    /// Inserts a value, replacing any existing value of the same type.
+    pub fn insert<T: Any + Send + Sync>(&self, value: T)
+    {
+        let Ok(mut inner) = self.inner.write() else { return };
+        let version = inner.bump();
+        inner.values.insert(TypeId::of::<T>(), (version, Box::new(value)));
+    }
+
+    /// Removes a value.
+    pub fn remove<T: Any + Send + Sync>(&self) -> Option<T>
+    {
+        let mut inner = self.inner.write().ok()?;
+        let (_, value) = inner.values.remove(&TypeId::of::<T>())?;
+        inner.bump();
+        value.downcast::<T>().ok().map(|value| *value)
+    }
+
+    /// Returns `true` if the store contains a value of type `T`.
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool
+    {
+        let Ok(inner) = self.inner.read() else { return false };
+        inner.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Gets a clone of a value.
+    pub fn get<T: Any + Send + Sync + Clone>(&self) -> Option<T>
+    {
+        self.read(|value: &T| value.clone())
+    }
+
+    /// Reads a value.
+    pub fn read<T: Any + Send + Sync, R>(&self, callback: impl FnOnce(&T) -> R) -> Option<R>
+    {
+        let inner = self.inner.read().ok()?;
+        let (_, value) = inner.values.get(&TypeId::of::<T>())?;
+        Some((callback)(value.downcast_ref::<T>()?))
+    }
+
+    /// Modifies a value.
+    ///
+    /// This will cause a [`SharedStoreChanged`] event to be sent in every world. Returns `None` without changing
+    /// the store's version if there is no value of type `T`.
+    pub fn modify<T: Any + Send + Sync, R>(&self, callback: impl FnOnce(&mut T) -> R) -> Option<R>
+    {
+        let mut inner = self.inner.write().ok()?;
+        let inner = &mut *inner;
+        let (value_version, value) = inner.values.get_mut(&TypeId::of::<T>())?;
+        let value = value.downcast_mut::<T>()?;
+
+        // The version is bumped by hand because `values` is borrowed.
+        inner.next_version += 1;
+        *value_version = inner.next_version;
+        Some((callback)(value))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that inserts a [`SharedStore`] into an app and sends [`SharedStoreChanged`] events.
+///
+/// Added automatically by [`WorldSwapPlugin`](crate::WorldSwapPlugin),
+/// [`ChildDefaultPlugins`](crate::ChildDefaultPlugins), and [`ChildWindowPlugins`](crate::ChildWindowPlugins).
+pub struct SharedStorePlugin
+{
+    store: SharedStore,
+}
+
+impl SharedStorePlugin
+{
+    /// Makes a plugin that uses a clone of the world's store.
+    ///
+    /// If the world doesn't have a [`SharedStore`] then the plugin uses a new store that isn't shared with any
+    /// other world.
+    pub fn new(world: &World) -> Self
+    {
+        let store = world.get_resource::<SharedStore>().cloned().unwrap_or_else(|| {
+            tracing::warn!("world has no SharedStore, SharedStorePlugin will use an unshared store");
+            SharedStore::default()
+        });
+        Self { store }
+    }
+
+    pub(crate) fn from_store(store: SharedStore) -> Self
+    {
+        Self { store }
+    }
+}
+
+impl Plugin for SharedStorePlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.insert_resource(self.store.clone())
+            .add_event::<SharedStoreChanged>()
+            .add_systems(First, detect_shared_store_changes);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
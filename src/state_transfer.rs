@@ -0,0 +1,93 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::state::state::{FreelyMutableState, NextState, State};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+type StateTransferFn = fn(&World, &mut World);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// State transfers registered in a world by [`StateTransferPlugin`].
+#[derive(Resource, Default)]
+struct StateTransfers(Vec<StateTransferFn>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn transfer_state<S: FreelyMutableState>(outgoing: &World, incoming: &mut World)
+{
+    // The outgoing world's pending state takes precedence over its current state.
+    let target = match outgoing.get_resource::<NextState<S>>() {
+        Some(NextState::Pending(pending)) => pending.clone(),
+        _ => {
+            let Some(state) = outgoing.get_resource::<State<S>>() else { return };
+            state.get().clone()
+        }
+    };
+
+    let Some(current) = incoming.get_resource::<State<S>>() else { return };
+    let Some(mut next) = incoming.get_resource_mut::<NextState<S>>() else { return };
+    if *current.get() == target {
+        next.reset();
+    } else {
+        next.set(target);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs state transfers registered in the incoming world.
+pub(crate) fn run_state_transfers(outgoing: &World, incoming: &mut World)
+{
+    let Some(transfers) = incoming.remove_resource::<StateTransfers>() else { return };
+    for transfer in transfers.0.iter() {
+        (transfer)(outgoing, incoming);
+    }
+    incoming.insert_resource(transfers);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that copies the state `S` from the outgoing foreground world into this world when it enters the
+/// foreground.
+///
+/// The outgoing world's pending [`NextState`] is copied if it has one, otherwise its current [`State`] is copied.
+/// The state is applied by setting this world's [`NextState`], so it will transition (running `OnExit`/`OnEnter`
+/// schedules) in its next `StateTransition` schedule. Nothing is copied if either world is missing the state.
+///
+/// Add this to every world that uses the state machine to keep them coherent across forks and joins.
+pub struct StateTransferPlugin<S: FreelyMutableState>
+{
+    _phantom: PhantomData<S>,
+}
+
+impl<S: FreelyMutableState> StateTransferPlugin<S>
+{
+    /// Makes a new plugin.
+    pub fn new() -> Self
+    {
+        Self { _phantom: PhantomData }
+    }
+}
+
+impl<S: FreelyMutableState> Default for StateTransferPlugin<S>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<S: FreelyMutableState> Plugin for StateTransferPlugin<S>
+{
+    fn build(&self, app: &mut App)
+    {
+        app.world_mut()
+            .get_resource_or_insert_with(StateTransfers::default)
+            .0
+            .push(transfer_state::<S>);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
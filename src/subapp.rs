@@ -1,12 +1,21 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+#[cfg(feature = "winit")]
 use bevy::a11y::AccessibilityRequested;
-use bevy::app::{AppExit, AppLabel, SubApp};
-use bevy::ecs::entity::EntityHashMap;
+use bevy::app::{AppExit, AppLabel, FixedMain, SubApp};
+use bevy::ecs::schedule::InternedScheduleLabel;
 use bevy::prelude::*;
-use bevy::time::{TimeReceiver, TimeSender};
+use bevy::time::{TimeReceiver, TimeSender, TimeUpdateStrategy};
 use bevy::utils::Instant;
+#[cfg(feature = "winit")]
 use bevy::window::{PrimaryWindow, RawHandleWrapper, WindowCreated};
+#[cfg(feature = "winit")]
 use bevy::winit::accessibility::{AccessKitAdapters, WinitActionRequestHandlers};
+#[cfg(feature = "winit")]
 use bevy::winit::{CachedWindow, EventLoopProxy, WakeUp, WinitEvent, WinitSettings, WinitWindows};
+#[cfg(feature = "winit")]
+use winit::window::WindowId;
 
 use crate::*;
 
@@ -31,15 +40,24 @@ fn intercept_app_exit(subapp_world: &World, world: &mut World)
     exit_events.clear();
 
     // Send join command.
-    subapp_world.resource::<SwapCommandSender>().send(SwapCommand::Join);
+    subapp_world
+        .resource::<SwapCommandSender>()
+        .with_source(Some(world.id()))
+        .send(SwapCommand::Join);
 
-    tracing::info!("converted AppExit from {:?} into SwapCommand::Join", world.id());
+    tracing::info!("converted AppExit from {:?} into SwapCommand::Join", WorldName::of(world));
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+#[cfg(feature = "render")]
 fn can_render(subapp_world: &World, main_world: &World) -> bool
 {
+    // Don't render if the main world disabled rendering.
+    if main_world.contains_resource::<RenderingDisabled>() {
+        return false;
+    }
+
     // Don't render if there is no render worker.
     let Some(target) = subapp_world.get_resource::<RenderWorkerTarget>() else { return false };
 
@@ -50,23 +68,103 @@ fn can_render(subapp_world: &World, main_world: &World) -> bool
     if target_id == RenderWorkerId::default() {
         return true;
     }
-    // Current main world id means the current main world's renderer is running.
-    if target_id == RenderWorkerId::from(main_world) {
+    // Current main world's worker id means the current main world's renderer is running.
+    if main_world.get_resource::<RenderWorkerId>() == Some(&target_id) {
         return true;
     }
 
     // Otherwise, a different world's renderer must be running.
+    let registry = subapp_world.resource::<WorldRegistry>();
+    match registry.resolve_render_worker(target_id) {
+        Some((world, status)) => {
+            let world = registry.name(world);
+            tracing::debug!("waiting for render worker {target_id:?} of {world:?} ({status:?}) to finish");
+        }
+        None => tracing::debug!("waiting for unknown render worker {target_id:?} to finish"),
+    }
+    false
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(not(feature = "render"))]
+fn can_render(_subapp_world: &World, _main_world: &World) -> bool
+{
     false
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+#[cfg(feature = "render")]
 fn extract_main_world_render_app(subapp_world: &mut World, main_world: &mut World)
 {
+    #[cfg(feature = "trace")]
+    let _span = tracing::info_span!("extract_main_world_render_app").entered();
+
     // Extract the current world and run the render app.
-    let Some(render_app) = &mut subapp_world.non_send_resource_mut::<ForegroundApp>().render_app else { return };
-    render_app.extract(main_world);
-    render_app.update();
+    let start = Instant::now();
+    let updated_at = {
+        let Some(render_app) = &mut subapp_world.non_send_resource_mut::<ForegroundApp>().render_app else {
+            return;
+        };
+        match main_world.get_resource::<RenderExtractAdapter>().copied() {
+            Some(adapter) => {
+                (adapter.0)(render_app, main_world);
+                None
+            }
+            None => {
+                render_app.extract(main_world);
+                let updated_at = Instant::now();
+                render_app.update();
+                Some(updated_at)
+            }
+        }
+    };
+
+    // Profile the render stages.
+    // - Adapters extract and update in one step, so their time is recorded as extract time.
+    match updated_at {
+        Some(updated_at) => {
+            record_profile_stage(subapp_world, ProfileStage::RenderExtract, updated_at - start);
+            record_profile_stage(subapp_world, ProfileStage::RenderUpdate, updated_at.elapsed());
+        }
+        None => record_profile_stage(subapp_world, ProfileStage::RenderExtract, start.elapsed()),
+    }
+}
+
+#[cfg(not(feature = "render"))]
+fn extract_main_world_render_app(_subapp_world: &mut World, _main_world: &mut World) {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Updates the [`RenderHealth`] of the main world.
+#[cfg(feature = "render")]
+fn update_render_health(subapp_world: &World, main_world: &mut World, extracted: bool)
+{
+    let has_render_app = subapp_world.non_send_resource::<ForegroundApp>().render_app.is_some();
+    let blocked_by = subapp_world.get_resource::<RenderWorkerTarget>().and_then(|target| {
+        let target_id = target.id();
+        let running = target_id != RenderWorkerId::default();
+        (running && main_world.get_resource::<RenderWorkerId>() != Some(&target_id)).then_some(target_id)
+    });
+    let health = RenderHealth { has_render_app, extracted: extracted && has_render_app, blocked_by };
+
+    // Avoid triggering change detection if nothing changed.
+    if main_world.get_resource::<RenderHealth>() != Some(&health) {
+        main_world.insert_resource(health);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs sub apps listed in [`WorldSwapPlugin::ordered_subapps`] on the main world.
+fn run_ordered_subapps(subapp_world: &mut World, main_world: &mut World)
+{
+    let Some(mut ordered) = subapp_world.get_non_send_resource_mut::<OrderedSubApps>() else { return };
+    for subapp in ordered.subapps.iter_mut() {
+        subapp.extract(main_world);
+        subapp.update();
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -79,6 +177,15 @@ fn send_time_to_main_world(subapp_world: &World)
 
 //-------------------------------------------------------------------------------------------------------------------
 
+fn forward_attention_requests(subapp_world: &World, main_world: &mut World)
+{
+    while let Ok(request) = subapp_world.resource::<AttentionReceiver>().try_recv() {
+        main_world.send_event(request);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 fn get_background_tick_rate(
     default_tick_rate: BackgroundTickRate,
     background_tick_rate_of_app: Option<BackgroundTickRate>,
@@ -89,44 +196,281 @@ fn get_background_tick_rate(
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn update_background_world(subapp_world: &mut World) -> bool
+fn background_exit(exit_code: BackgroundExitCode, background_world: &World) -> AppExit
 {
-    if *subapp_world.resource::<WorldSwapSubAppState>() == WorldSwapSubAppState::Exiting {
-        return true;
+    match exit_code {
+        BackgroundExitCode::Success => AppExit::Success,
+        BackgroundExitCode::Error(code) => AppExit::Error(code),
+        BackgroundExitCode::Forward => {
+            let events = background_world.resource::<Events<AppExit>>();
+            events.get_reader().read(events).last().cloned().unwrap_or(AppExit::Success)
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs the background world's main schedule.
+///
+/// Time is sent to the world through its cached time channel (if it has one), since worlds that render expect to
+/// receive time from their render app.
+fn run_background_main(background_app: &mut WorldSwapApp)
+{
+    // Only take the receiver if there is a sender, otherwise the receiver would be lost.
+    if let Some(time_sender) = &background_app.time_sender {
+        if let Some(time_receiver) = background_app.time_receiver.take() {
+            let _ = time_sender.0.send(Instant::now());
+            background_app.world.insert_resource(time_receiver);
+        }
+    }
+
+    set_panic_context(&background_app.world, Main);
+    run_main_with_schedule_filter(&mut background_app.world);
+
+    // Park the receiver again so it isn't used while the world is swapped.
+    if let Some(time_receiver) = background_app.world.remove_resource::<TimeReceiver>() {
+        background_app.time_receiver = Some(time_receiver);
+    }
+
+    run_background_compute(background_app);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs a single schedule in the background world for [`BackgroundTickRate::Schedule`].
+///
+/// Returns `true` if the schedule ran.
+fn run_background_schedule(
+    background_app: &mut WorldSwapApp,
+    label: InternedScheduleLabel,
+    interval: Duration,
+) -> bool
+{
+    let now = Instant::now();
+    if background_app
+        .last_schedule_run
+        .is_some_and(|last_run| now.saturating_duration_since(last_run) < interval)
+    {
+        return false;
+    }
+    background_app.last_schedule_run = Some(now);
+
+    set_panic_context(&background_app.world, label);
+    if let Err(err) = background_app.world.try_run_schedule(label) {
+        tracing::warn!("failed running background schedule: {err}");
+    }
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs the render app of a [`ComputeOnly`] world that isn't in the foreground.
+#[cfg(feature = "render")]
+fn run_background_compute(background_app: &mut WorldSwapApp)
+{
+    if !background_app.world.contains_resource::<ComputeOnly>() {
+        return;
+    }
+    let Some(render_app) = background_app.render_app.as_mut() else { return };
+    match background_app.world.get_resource::<RenderExtractAdapter>().copied() {
+        Some(adapter) => (adapter.0)(render_app, &mut background_app.world),
+        None => {
+            render_app.extract(&mut background_app.world);
+            render_app.update();
+        }
+    }
+}
+
+#[cfg(not(feature = "render"))]
+fn run_background_compute(_background_app: &mut WorldSwapApp) {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes the background world's next update advance time by the same amount as the main world's last update.
+fn sync_lockstep_time(main_world: &World, background_world: &mut World)
+{
+    let delta = main_world.resource::<Time<Real>>().delta();
+    background_world.insert_resource(TimeUpdateStrategy::ManualDuration(delta));
+
+    let timestep = main_world.resource::<Time<Fixed>>().timestep();
+    background_world.resource_mut::<Time<Fixed>>().set_timestep(timestep);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sleeps until the frame time in [`WorldSwapFramePolicy`] has elapsed, then starts the next frame.
+fn pace_frame(subapp_world: &mut World)
+{
+    let policy = subapp_world.resource::<WorldSwapPlugin>().frame_policy;
+    let mut pacing = subapp_world.resource_mut::<FramePacing>();
+
+    if let (Some(frame_time), Some(frame_start)) = (policy.min_frame_time, pacing.frame_start) {
+        let remaining = frame_time.saturating_sub(frame_start.elapsed());
+        if !remaining.is_zero() {
+            #[cfg(feature = "trace")]
+            let _span = tracing::info_span!("pace_frame").entered();
+            std::thread::sleep(remaining);
+        }
+    }
+
+    pacing.frame_start = Some(Instant::now());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returns the `AppExit` that should be sent to the main world if the app should shut down.
+fn update_background_world(subapp_world: &mut World, main_world: &mut World) -> Option<AppExit>
+{
+    #[cfg(feature = "trace")]
+    let _span = tracing::info_span!("update_background_world").entered();
+
+    // Keep reporting the exit that shut down the app.
+    if let WorldSwapSubAppState::Exiting(exit) = subapp_world.resource::<WorldSwapSubAppState>() {
+        return Some(exit.clone());
+    }
+
+    // Skip the update if background updates were paused with a developer hotkey.
+    #[cfg(feature = "hotkeys")]
+    if background_updates_paused(subapp_world) {
+        return None;
     }
 
     let close_on_exit = subapp_world.resource::<WorldSwapPlugin>().abort_on_background_exit;
+    let exit_code = subapp_world.resource::<WorldSwapPlugin>().background_exit_code;
     let default_tick_rate = subapp_world.resource::<WorldSwapPlugin>().background_tick_rate;
+    let has_headroom = subapp_world
+        .resource::<FramePacing>()
+        .has_background_headroom(subapp_world.resource::<WorldSwapPlugin>().frame_policy);
+    let pending_commands = subapp_world.resource::<SwapCommandReceiver>().len();
     let Some(background_app) = &mut subapp_world.non_send_resource_mut::<BackgroundApp>().app else {
-        return false;
+        return None;
     };
 
     // Detect AppExit in the background world.
     // - Do this before updating the background world in case AppExit was sent in a previous update.
     if !background_app.world.resource::<Events<AppExit>>().is_empty() {
-        return close_on_exit;
+        return close_on_exit.then(|| background_exit(exit_code, &background_app.world));
+    }
+
+    // Skip the update if there isn't enough frame time left.
+    if !has_headroom {
+        return None;
     }
 
+    // Skip the update if the world is parked while idle.
+    if is_idle_parked(&mut background_app.world) {
+        return None;
+    }
+    let idle_tracking = begin_idle_tracking(&background_app.world);
+
     // Update the background app.
-    match get_background_tick_rate(default_tick_rate, background_app.background_tick_rate) {
-        BackgroundTickRate::Never { .. } => (),
+    let start = Instant::now();
+    let updated = match get_background_tick_rate(default_tick_rate, background_app.background_tick_rate) {
+        BackgroundTickRate::Never { .. } => false,
         BackgroundTickRate::EveryTick => {
-            background_app.world.run_schedule(Main);
+            run_background_main(background_app);
+            true
         }
+        BackgroundTickRate::Lockstep => {
+            sync_lockstep_time(main_world, &mut background_app.world);
+            run_background_main(background_app);
+            true
+        }
+        BackgroundTickRate::Schedule(label, interval) => run_background_schedule(background_app, label, interval),
+    };
+    let duration = start.elapsed();
+
+    // Track whether the update did anything.
+    if let Some(idle_tracking) = idle_tracking {
+        end_idle_tracking(&mut background_app.world, idle_tracking);
     }
 
     // Check if AppExit was emitted during the update.
-    if !background_app.world.resource::<Events<AppExit>>().is_empty() {
-        return close_on_exit;
+    let sent_exit = !background_app.world.resource::<Events<AppExit>>().is_empty();
+    let exit = (sent_exit && close_on_exit).then(|| background_exit(exit_code, &background_app.world));
+
+    // Report the update to the foreground world.
+    // - Commands sent by the background world are still in the channel, so they are detected by comparing its
+    //   length. Commands sent from other threads during the update are counted too.
+    if updated {
+        let world = background_app.world.id();
+        let label = background_app.world.get_resource::<WorldLabel>().cloned();
+        let sent_swap_commands = subapp_world.resource::<SwapCommandReceiver>().len() > pending_commands;
+        main_world.send_event(BackgroundTickReport { world, label, duration, sent_exit, sent_swap_commands });
+        #[cfg(feature = "metrics")]
+        record_background_tick(duration);
     }
 
-    false
+    exit
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Re-adds the `Window` component to a window entity that lost its OS window, so the OS window is created again
+/// with the same config when the world next updates.
+#[cfg(feature = "winit")]
+fn request_window_recreation(world: &mut World, entity: Entity)
+{
+    let Some(mut entity) = world.get_entity_mut(entity) else { return };
+    let Some(window) = entity.take::<Window>() else { return };
+    entity.remove::<(CachedWindow, RawHandleWrapper)>();
+    entity.insert(window);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Closes OS windows created by a world that isn't in the foreground, according to its
+/// [`PreForegroundWindowPolicy`].
+#[cfg(feature = "winit")]
+fn close_pre_foreground_windows(subapp_world: &World, new_world: &mut World)
+{
+    let Some(mut new_windows) = new_world.remove_non_send_resource::<WinitWindows>() else { return };
+    if new_windows.windows.is_empty() {
+        new_world.insert_non_send_resource(new_windows);
+        return;
+    }
+
+    let policy = subapp_world.resource::<WorldSwapPlugin>().pre_foreground_windows;
+    let world = WorldName::of(new_world);
+    match policy {
+        PreForegroundWindowPolicy::Error => {
+            tracing::error!("{world:?} created windows while not in the foreground; closing them");
+        }
+        PreForegroundWindowPolicy::Despawn => {
+            tracing::warn!("{world:?} created windows while not in the foreground; closing them");
+        }
+        PreForegroundWindowPolicy::Defer => {
+            tracing::debug!("{world:?} created windows while not in the foreground; deferring them");
+        }
+    }
+
+    // Drop the OS windows.
+    let window_ids: Vec<_> = new_windows.windows.keys().copied().collect();
+    new_windows.windows.clear();
+
+    for window_id in window_ids {
+        let Some(entity) = new_windows.winit_to_entity.remove(&window_id) else { continue };
+        new_windows.entity_to_winit.remove(&entity);
+
+        match policy {
+            PreForegroundWindowPolicy::Error | PreForegroundWindowPolicy::Despawn => {
+                new_world.despawn(entity);
+            }
+            PreForegroundWindowPolicy::Defer => request_window_recreation(new_world, entity),
+        }
+    }
+
+    new_world.insert_non_send_resource(new_windows);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "winit")]
 fn transfer_windows(main_world: &mut World, new_world: &mut World)
 {
+    #[cfg(feature = "trace")]
+    let _span = tracing::info_span!("transfer_windows").entered();
+
     // Make sure the new world uses the same accessibility toggle, since it is embedded in accessibility nodes for
     // existing windows.
     if let Some(accessibility_toggle) = main_world.get_resource::<AccessibilityRequested>() {
@@ -134,27 +478,42 @@ fn transfer_windows(main_world: &mut World, new_world: &mut World)
     }
 
     // Extract WinitWindows.
+    // - Swaps are rejected by `validate_window_transfer` if the new world doesn't have WinitWindows.
+    // - Windows the new world created while away were already closed by `close_pre_foreground_windows`.
     let Some(mut main_windows) = main_world.remove_non_send_resource::<WinitWindows>() else { return };
     let mut new_windows = new_world
         .remove_non_send_resource::<WinitWindows>()
-        .expect("if the main world has WinitWindows, the new world should too");
-
-    // Validate that the new world did not create any windows.
-    if !new_windows.windows.is_empty() {
-        panic!("a world that isn't in the foreground created windows");
-    }
+        .unwrap_or_default();
 
     // Move winit windows to the new world.
     new_windows.windows = std::mem::take(&mut main_windows.windows);
 
     // Despawn window entities in the new world if they don't have windows.
-    for (entity, window_id) in new_windows.entity_to_winit.iter() {
-        if new_windows.windows.contains_key(window_id) {
+    // - Entities are despawned in index order so reactions are deterministic.
+    let mut stale_windows: Vec<(Entity, WindowId)> = new_windows
+        .entity_to_winit
+        .iter()
+        .filter(|(_, window_id)| !new_windows.windows.contains_key(*window_id))
+        .map(|(entity, window_id)| (*entity, *window_id))
+        .collect();
+    stale_windows.sort_by_key(|(entity, _)| *entity);
+    let stale_window_handler = new_world.get_resource::<StaleWindowHandler>().copied();
+    let reopen_windows = new_world.contains_resource::<ReopenClosedWindows>();
+
+    for (entity, window_id) in stale_windows {
+        new_windows.winit_to_entity.remove(&window_id);
+
+        // Worlds that reopen their windows keep the entities.
+        if reopen_windows {
+            request_window_recreation(new_world, entity);
             continue;
         }
 
-        new_world.despawn(*entity);
-        new_windows.winit_to_entity.remove(window_id);
+        if let Some(handler) = stale_window_handler {
+            (handler.0)(new_world, entity);
+        }
+
+        new_world.despawn(entity);
 
         // NOTE: WindowClosed events don't need to be sent, as they will be sent automatically by WinitCorePlugin
     }
@@ -187,8 +546,14 @@ fn transfer_windows(main_world: &mut World, new_world: &mut World)
             // Windows that are already known to the new world.
 
             // Overwrite Window components.
-            let mut new_entity = new_world.get_entity_mut(*new_entity).unwrap();
-            new_entity.insert(window.clone());
+            // - Windows that weren't changed in either world since they were last copied between the worlds are
+            //   skipped.
+            let new_entity_id = *new_entity;
+            if !windows_in_sync(main_world, *main_entity, new_world, new_entity_id) {
+                new_world.entity_mut(new_entity_id).insert(window.clone());
+                record_window_sync(main_world, *main_entity, new_world, new_entity_id);
+            }
+            let mut new_entity = new_world.entity_mut(new_entity_id);
 
             // Synchronize RawHandleWrapper component.
             if let Some(raw_handle_wrapper) = maybe_raw_handle_wrapper {
@@ -199,7 +564,9 @@ fn transfer_windows(main_world: &mut World, new_world: &mut World)
 
             // Synchronize PrimaryWindow component.
             if let Some(primary) = maybe_primary {
-                new_entity.insert(*primary);
+                if !new_entity.contains::<PrimaryWindow>() {
+                    new_entity.insert(*primary);
+                }
             } else {
                 new_entity.remove::<PrimaryWindow>();
             }
@@ -220,6 +587,7 @@ fn transfer_windows(main_world: &mut World, new_world: &mut World)
 
             let entity_id = entity_cmds.id();
             new_windows.winit_to_entity.insert(*window_id, entity_id);
+            record_window_sync(main_world, *main_entity, new_world, entity_id);
 
             // Send WindowCreated event to the new world.
             // - We must do this manually because we bypass the Bevy code path that emits these events, because
@@ -240,27 +608,33 @@ fn transfer_windows(main_world: &mut World, new_world: &mut World)
     debug_assert_eq!(new_windows.entity_to_winit.len(), new_windows.windows.len());
 
     // Transfer AccessKitAdapters to the new world.
-    if let Some(mut access_kit) = main_world.remove_non_send_resource::<AccessKitAdapters>() {
-        let mut new_access_kit = EntityHashMap::default();
+    // - Adapters are merged into the new world's adapters, which it keeps while away from the foreground.
+    // - The outgoing world keeps an empty AccessKitAdapters so its accessibility systems still work when it
+    //   updates in the background, and so adapters can be merged back into it when it re-enters the foreground.
+    // - Adapters for windows that no longer exist are dropped.
+    if let Some(mut access_kit) = main_world.get_non_send_resource_mut::<AccessKitAdapters>() {
+        let mut new_access_kit = new_world.remove_non_send_resource::<AccessKitAdapters>().unwrap_or_default();
         for (entity, adapter) in access_kit.drain() {
             let Some(new_entity) = map_winit_window_entities(&main_windows, &new_windows, entity) else {
                 continue;
             };
             new_access_kit.insert(new_entity, adapter);
         }
-        new_world.insert_non_send_resource(AccessKitAdapters(new_access_kit));
+        new_world.insert_non_send_resource(new_access_kit);
     }
 
     // Transfer WinitActionRequestHandlers to the new world.
-    if let Some(mut action_handlers) = main_world.remove_resource::<WinitActionRequestHandlers>() {
-        let mut new_action_handlers = EntityHashMap::default();
+    // - Handlers are merged the same way as AccessKitAdapters.
+    if let Some(mut action_handlers) = main_world.get_resource_mut::<WinitActionRequestHandlers>() {
+        let mut new_action_handlers =
+            new_world.remove_resource::<WinitActionRequestHandlers>().unwrap_or_default();
         for (entity, handler) in action_handlers.drain() {
             let Some(new_entity) = map_winit_window_entities(&main_windows, &new_windows, entity) else {
                 continue;
             };
             new_action_handlers.insert(new_entity, handler);
         }
-        new_world.insert_resource(WinitActionRequestHandlers(new_action_handlers));
+        new_world.insert_resource(new_action_handlers);
     }
 
     // Return WinitWindows.
@@ -270,30 +644,166 @@ fn transfer_windows(main_world: &mut World, new_world: &mut World)
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn drain_cached_window_events(main_world: &mut World, new_world: &mut World)
+#[cfg(feature = "winit")]
+fn drain_cached_window_events(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    new_world: &mut World,
+    behavior: SwapBehavior,
+)
 {
-    // Get WinitWindows for entity mapping.
-    let Some(main_windows) = main_world.remove_non_send_resource::<WinitWindows>() else { return };
+    if !main_world.contains_non_send::<WinitWindows>() {
+        return;
+    }
+
+    // Take the events collected while the main world was in the foreground.
+    let digest = std::mem::take(&mut *main_world.resource_mut::<WindowEventCache>());
+
+    // Worlds that stay in the background will receive the events when they next enter the foreground.
+    if let Some(background_app) = subapp_world.non_send_resource_mut::<BackgroundApp>().app.as_mut() {
+        if let Some(mut background_events) = background_app.world.get_resource_mut::<WindowEventCache>() {
+            background_events.extend(digest.clone());
+        }
+    }
+
+    // Discard events the new world missed if they shouldn't be replayed.
+    // - Deferred events that weren't replayed before the world left the foreground are also discarded.
+    let deferred = new_world.remove_resource::<DeferredWindowEventReplay>();
+    if !behavior.replay_window_events {
+        new_world.insert_resource(WindowEventCache::default());
+        return;
+    }
+
+    // Send window events.
+    // - Deferred events from the new world's previous time in the foreground are sent first, followed by events
+    //   cached for the new world while it was in the background.
+    // - The new world's cache is left empty so it can collect events while in the foreground.
+    let mut new_events = deferred.map(|deferred| deferred.0).unwrap_or_default();
+    new_events.extend(new_world.remove_resource::<WindowEventCache>().unwrap_or_default());
+    new_events.extend(digest);
+
+    // Deferred events are sent after the new world's first update.
+    if behavior.defer_window_event_replay {
+        new_world.insert_resource(DeferredWindowEventReplay(new_events));
+        new_world.insert_resource(WindowEventCache::default());
+        return;
+    }
     let new_windows = new_world
         .remove_non_send_resource::<WinitWindows>()
         .expect("if main world has WinitWindows, new worlds should too");
-
-    // Send window events
-    let mut main_window_events = main_world.resource_mut::<WindowEventCache>();
-    main_window_events.dispatch(&main_windows, &new_windows, new_world);
+    new_events.dispatch(&new_windows, new_world);
 
     // Put WinitWindows back.
-    main_world.insert_non_send_resource(main_windows);
     new_world.insert_non_send_resource(new_windows);
+    new_world.insert_resource(new_events);
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn prepare_world_swap(subapp_world: &mut World, main_world: &mut World, new_world: &mut World)
+/// Sends window events whose replay was deferred by [`SwapBehavior::defer_window_event_replay`].
+#[cfg(feature = "winit")]
+fn replay_deferred_window_events(main_world: &mut World)
 {
-    // SwapCommandSender is needed in the new world.
-    new_world.insert_resource(subapp_world.resource::<SwapCommandSender>().clone());
+    let Some(DeferredWindowEventReplay(mut events)) = main_world.remove_resource::<DeferredWindowEventReplay>()
+    else {
+        return;
+    };
+    let Some(windows) = main_world.remove_non_send_resource::<WinitWindows>() else { return };
+    events.dispatch(&windows, main_world);
+    main_world.insert_non_send_resource(windows);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Releases all pressed buttons in a world.
+fn reset_input(world: &mut World)
+{
+    if let Some(mut keys) = world.get_resource_mut::<ButtonInput<KeyCode>>() {
+        keys.reset_all();
+    }
+    if let Some(mut buttons) = world.get_resource_mut::<ButtonInput<MouseButton>>() {
+        buttons.reset_all();
+    }
+    if let Some(mut buttons) = world.get_resource_mut::<ButtonInput<GamepadButton>>() {
+        buttons.reset_all();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Inserts resources shared by all worlds.
+fn insert_backend_resources(subapp_world: &World, new_world: &mut World)
+{
+    // SwapCommandSender and AttentionSender are needed in the new world.
+    new_world.insert_resource(
+        subapp_world
+            .resource::<SwapCommandSender>()
+            .with_source(Some(new_world.id())),
+    );
+    new_world.insert_resource(subapp_world.resource::<AttentionSender>().clone());
+    new_world.insert_resource(subapp_world.resource::<WorldSwapRemoteHandle>().clone());
+    #[cfg(feature = "inspect")]
+    new_world.insert_resource(subapp_world.resource::<BackgroundInspector>().clone());
+
+    // All worlds share the same SharedStore.
+    if !new_world.contains_resource::<SharedStore>() {
+        new_world.insert_resource(subapp_world.resource::<SharedStore>().clone());
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg_attr(not(feature = "winit"), allow(unused_variables))]
+fn prepare_world_swap(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    new_world: &mut World,
+    behavior: SwapBehavior,
+)
+{
+    #[cfg(feature = "trace")]
+    let _span = tracing::info_span!("prepare_world_swap").entered();
+
+    insert_backend_resources(subapp_world, new_world);
+
+    // Copy states from the outgoing world.
+    #[cfg(feature = "states")]
+    run_state_transfers(main_world, new_world);
+
+    // Move non-send resources from the outgoing world.
+    run_non_send_handoffs(main_world, new_world);
+
+    // Worlds in the foreground aren't parked.
+    reset_idle_parking(new_world);
+
+    // Record render workers so they can be resolved while waiting for them.
+    #[cfg(feature = "render")]
+    {
+        let mut registry = subapp_world.resource_mut::<WorldRegistry>();
+        registry.record_render_worker(main_world);
+        registry.record_render_worker(new_world);
+    }
+
+    // Hook the new world up to windows.
+    #[cfg(feature = "winit")]
+    prepare_windows(subapp_world, main_world, new_world, behavior);
+
+    // Release buttons that were pressed before the new world entered the foreground.
+    if behavior.reset_input {
+        reset_input(new_world);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
 
+#[cfg(feature = "winit")]
+fn prepare_windows(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    new_world: &mut World,
+    behavior: SwapBehavior,
+)
+{
     // Connect the new world to the winit event loop.
     if let Some(event_loop_proxy) = main_world.get_non_send_resource::<EventLoopProxy<WakeUp>>() {
         new_world.insert_non_send_resource(event_loop_proxy.clone());
@@ -308,18 +818,272 @@ fn prepare_world_swap(subapp_world: &mut World, main_world: &mut World, new_worl
         }
     }
 
+    // Remove windows the new world created before entering the foreground.
+    close_pre_foreground_windows(subapp_world, new_world);
+
     // Update window entities in the new world.
+    // - Window geometry is recorded before the outgoing world loses its windows, and applied after the new world
+    //   gets them.
+    record_window_geometry(main_world);
     transfer_windows(main_world, new_world);
+    apply_window_geometry(new_world);
 
     // Drain cached window events into the new world.
     // - This must be done after updating window entities in the new world, so event entities can be mapped
     //   properly.
-    // - Note that window events will ping-pong when swapping worlds since we don't have a way to know if a window
-    //   event is ping-ponged or emitted by the app. This should at most cause systems that react to those events
-    //   to run redundantly every time you swap.
-    //todo: fix event ping-ponging? can cache last-seen event values in WindowEventCache, and don't dispatch
-    // events if the values won't change
-    drain_cached_window_events(main_world, new_world);
+    // - Events are stamped when first collected, and worlds skip events they already saw, so events don't
+    //   ping-pong between worlds that swap rapidly (e.g. fork then join).
+    drain_cached_window_events(subapp_world, main_world, new_world, behavior);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Checks that the main world's windows can be moved to a world that is entering the foreground.
+#[cfg(feature = "winit")]
+fn validate_window_transfer(main_world: &World, new_world: &World) -> Result<(), SwapErrorReason>
+{
+    let Some(main_windows) = main_world.get_non_send_resource::<WinitWindows>() else { return Ok(()) };
+
+    // Window plumbing is needed to receive windows from the main world.
+    if !new_world.contains_non_send::<WinitWindows>() {
+        return Err(SwapErrorReason::MissingWindowPlumbing);
+    }
+
+    // Every OS window needs a window entity in the main world, otherwise the window would be lost.
+    let windows_match = main_windows.windows.keys().all(|window_id| {
+        main_windows
+            .winit_to_entity
+            .get(window_id)
+            .is_some_and(|entity| {
+                main_world.get::<Window>(*entity).is_some() && main_world.get::<CachedWindow>(*entity).is_some()
+            })
+    });
+    if !windows_match {
+        return Err(SwapErrorReason::WindowMismatch);
+    }
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Checks that the main world's windows can be moved to the background world.
+///
+/// Sends a [`SwapError`] and returns `false` if they can't.
+#[cfg(feature = "winit")]
+fn validate_background_windows(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    origin: SwapOrigin,
+    command: SwapCommandKind,
+) -> bool
+{
+    let result = match subapp_world.non_send_resource::<BackgroundApp>().app.as_ref() {
+        Some(background_app) => validate_window_transfer(main_world, &background_app.world),
+        None => Ok(()),
+    };
+    let Err(reason) = result else { return true };
+    tracing::error!("discarding {:?} command {:?}: {reason}", command, origin.id);
+    send_swap_error(subapp_world, main_world, origin, command, reason);
+    false
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Checks that an incoming app can be swapped into the foreground.
+#[cfg_attr(not(feature = "winit"), allow(unused_variables))]
+fn validate_incoming_app(
+    subapp_world: &World,
+    main_world: &World,
+    new_app: &WorldSwapApp,
+) -> Result<(), SwapErrorReason>
+{
+    // The main world's windows must be transferable to the new world.
+    #[cfg(feature = "winit")]
+    validate_window_transfer(main_world, &new_app.world)?;
+    #[cfg(feature = "winit")]
+    if main_world.contains_non_send::<WinitWindows>() {
+        let policy = subapp_world.resource::<WorldSwapPlugin>().pre_foreground_windows;
+        let created_windows = new_app
+            .world
+            .get_non_send_resource::<WinitWindows>()
+            .is_some_and(|new_windows| !new_windows.windows.is_empty());
+        if created_windows && policy == PreForegroundWindowPolicy::Error {
+            return Err(SwapErrorReason::WindowsCreatedOutsideForeground);
+        }
+    }
+
+    // The new world's swap commands must come to this backend.
+    if let Some(sender) = new_app.world.get_resource::<SwapCommandSender>() {
+        if !sender.sender.same_channel(&subapp_world.resource::<SwapCommandSender>().sender) {
+            return Err(SwapErrorReason::ForeignSwapCommandSender);
+        }
+    }
+
+    // The new world's renderer must coordinate with our render worker target.
+    #[cfg(feature = "render")]
+    if new_app.render_app.is_some() {
+        let Some(target) = subapp_world.get_resource::<RenderWorkerTarget>() else {
+            return Err(SwapErrorReason::RenderWorkerTargetMismatch);
+        };
+        let Some(new_target) = new_app.world.get_resource::<RenderWorkerTarget>() else {
+            return Err(SwapErrorReason::RenderWorkerTargetMismatch);
+        };
+        if !target.is_same(new_target) {
+            return Err(SwapErrorReason::RenderWorkerTargetMismatch);
+        }
+    }
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn send_swap_error(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    origin: SwapOrigin,
+    command: SwapCommandKind,
+    reason: SwapErrorReason,
+)
+{
+    #[cfg(feature = "metrics")]
+    record_command_error(command, reason);
+    let error = SwapError { id: origin.id, command, reason };
+
+    // Send to the background world if it sent the command.
+    if let Some(background_app) = subapp_world.non_send_resource_mut::<BackgroundApp>().app.as_mut() {
+        if origin.source == Some(background_app.world.id()) {
+            background_app.world.send_event(error);
+            return;
+        }
+    }
+
+    main_world.send_event(error);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn send_swap_applied(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    origin: SwapOrigin,
+    command: SwapCommandKind,
+)
+{
+    #[cfg(feature = "metrics")]
+    if let Some(CommandTimer(start)) = subapp_world.get_resource::<CommandTimer>() {
+        record_command_applied(command, start.elapsed());
+    }
+    let applied = SwapApplied { id: origin.id, command };
+
+    // Send to the world that sent the command (or the foreground world if the sender is unknown).
+    match origin.source {
+        None => {
+            main_world.send_event(applied);
+        }
+        Some(source) if source == main_world.id() => {
+            main_world.send_event(applied);
+        }
+        Some(source) => {
+            let Some(background_app) = subapp_world.non_send_resource_mut::<BackgroundApp>().app.as_mut() else {
+                return;
+            };
+            if background_app.world.id() == source {
+                background_app.world.send_event(applied);
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Warns if an incoming app will exit as soon as it enters the foreground.
+fn detect_stale_app_exit(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    origin: SwapOrigin,
+    command: SwapCommandKind,
+    new_app: &WorldSwapApp,
+)
+{
+    if !new_app.has_exit() {
+        return;
+    }
+
+    tracing::warn!("{:?} command {:?} brought in {:?} with stale AppExit events; use WorldSwapApp::clear_exit \
+        before re-using worlds", command, origin.id, WorldName::of(&new_app.world));
+    let warning = StaleAppExit { id: origin.id, command, world: new_app.world.id() };
+
+    // Send to the background world if it sent the command.
+    if let Some(background_app) = subapp_world.non_send_resource_mut::<BackgroundApp>().app.as_mut() {
+        if origin.source == Some(background_app.world.id()) {
+            background_app.world.send_event(warning);
+            return;
+        }
+    }
+
+    main_world.send_event(warning);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn reject_incoming_app(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    origin: SwapOrigin,
+    command: SwapCommandKind,
+    new_app: WorldSwapApp,
+    reason: SwapErrorReason,
+)
+{
+    let world = WorldName::of(&new_app.world);
+    tracing::error!("discarding {:?} command {:?} with {:?}: {reason}", command, origin.id, world);
+    send_swap_error(subapp_world, main_world, origin, command, reason);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn run_fixed_catch_up(world: &mut World, elapsed: Duration)
+{
+    let Some(catch_up) = world.get_resource::<FixedCatchUp>().copied() else { return };
+    let Some(timestep) = world.get_resource::<Time<Fixed>>().map(|time| time.timestep()) else { return };
+    if timestep.is_zero() {
+        return;
+    }
+
+    let steps = (elapsed.as_nanos() / timestep.as_nanos()).min(catch_up.max_steps as u128) as u32;
+    if steps == 0 {
+        return;
+    }
+    tracing::debug!("running {steps} fixed catch-up steps in {:?}", WorldName::of(world));
+
+    // Run FixedMain the same way `RunFixedMainLoop` does, with the generic clock set to fixed time.
+    for _ in 0..steps {
+        world.resource_mut::<Time<Fixed>>().advance_by(timestep);
+        let fixed_time = world.resource::<Time<Fixed>>().as_generic();
+        world.insert_resource(fixed_time);
+        world.run_schedule(FixedMain);
+    }
+    let virtual_time = world.resource::<Time<Virtual>>().as_generic();
+    world.insert_resource(virtual_time);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn run_warmup_ticks(subapp_world: &World, new_app: &mut WorldSwapApp)
+{
+    let ticks = std::mem::take(&mut new_app.warmup_ticks);
+    if ticks == 0 {
+        return;
+    }
+
+    insert_backend_resources(subapp_world, &mut new_app.world);
+
+    tracing::debug!("running {ticks} warmup ticks for {:?}", WorldName::of(&new_app.world));
+    for _ in 0..ticks {
+        run_background_main(new_app);
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -332,6 +1096,16 @@ fn take_background_app(subapp_world: &mut World) -> Option<WorldSwapApp>
     if background_app.paused_by_tick_policy {
         background_app.world.resource_mut::<Time<Virtual>>().unpause();
         background_app.paused_by_tick_policy = false;
+
+        // Catch up on fixed updates missed while paused.
+        if let Some(paused_at) = background_app.paused_at.take() {
+            run_fixed_catch_up(&mut background_app.world, paused_at.elapsed());
+        }
+    }
+
+    // Restore the world's time strategy if it was in lockstep.
+    if let Some(strategy) = background_app.time_update_strategy.take() {
+        background_app.world.insert_resource(strategy);
     }
 
     Some(background_app)
@@ -369,14 +1143,33 @@ fn swap_worlds(subapp_world: &mut World, main_world: &mut World, mut new_app: Wo
     new_app.time_receiver = new_app.world.remove_resource::<TimeReceiver>();
 
     // Swap render apps.
-    let new_render_app = new_app.render_app.take();
-    new_app.render_app = subapp_world.non_send_resource_mut::<ForegroundApp>().render_app.take();
-    subapp_world.non_send_resource_mut::<ForegroundApp>().render_app = new_render_app;
+    #[cfg(feature = "render")]
+    {
+        let new_render_app = new_app.render_app.take();
+        new_app.render_app = subapp_world.non_send_resource_mut::<ForegroundApp>().render_app.take();
+        subapp_world.non_send_resource_mut::<ForegroundApp>().render_app = new_render_app;
+    }
 
     // Update statuses.
     main_world.insert_resource(WorldSwapStatus::Foreground);
     new_app.world.insert_resource(WorldSwapStatus::Suspended);
 
+    // Track time away from the foreground.
+    let now = Instant::now();
+    if let Some(left_at) = new_app.left_foreground_at.replace(now) {
+        let away = now.saturating_duration_since(left_at);
+        let mut time_away = main_world.get_resource_or_insert_with(TimeAwayFromForeground::default);
+        time_away.last = away;
+        time_away.total += away;
+    }
+
+    // Only the foreground world collects gizmos.
+    #[cfg(feature = "gizmos")]
+    {
+        enable_gizmos(main_world);
+        disable_gizmos(&mut new_app.world);
+    }
+
     new_app
 }
 
@@ -400,18 +1193,32 @@ fn add_app_to_background(subapp_world: &mut World, mut background_app: WorldSwap
 {
     // Prep background status.
     background_app.world.insert_resource(WorldSwapStatus::Background);
+    #[cfg(feature = "gizmos")]
+    disable_gizmos(&mut background_app.world);
 
     // Pause the background app if necessary.
     background_app.paused_by_tick_policy = false;
+    background_app.last_schedule_run = None;
     if freeze_time_in_background(subapp_world, background_app.background_tick_rate) {
         let mut time = background_app.world.resource_mut::<Time<Virtual>>();
 
         if !time.is_paused() {
             time.pause();
             background_app.paused_by_tick_policy = true;
+            background_app.paused_at = Some(Instant::now());
         }
     }
 
+    // Cache the world's time strategy if it will be driven in lockstep.
+    let rate = get_background_tick_rate(
+        subapp_world.resource::<WorldSwapPlugin>().background_tick_rate,
+        background_app.background_tick_rate,
+    );
+    if let BackgroundTickRate::Lockstep = rate {
+        background_app.time_update_strategy =
+            Some(background_app.world.remove_resource::<TimeUpdateStrategy>().unwrap_or_default());
+    }
+
     // Insert the background app.
     let prev_background = subapp_world
         .non_send_resource_mut::<BackgroundApp>()
@@ -431,8 +1238,13 @@ fn handle_swap_pass_recovery(subapp_world: &mut World, main_world: &mut World, p
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn handle_swap_join_recovery(subapp_world: &mut World, main_world: &mut World, joined_app: WorldSwapApp)
+fn handle_swap_join_recovery(subapp_world: &mut World, main_world: &mut World, mut joined_app: WorldSwapApp)
 {
+    // Minigame results and play-mode changes are delivered even if there is no recovery callback.
+    deliver_minigame_result(&mut joined_app.world, main_world);
+    #[cfg(feature = "play_mode")]
+    run_play_mode_copy_back(&mut joined_app.world, main_world);
+
     let Some(recovery_fn) = subapp_world.resource::<WorldSwapPlugin>().swap_join_recovery else { return };
 
     (recovery_fn)(main_world, joined_app);
@@ -440,105 +1252,412 @@ fn handle_swap_join_recovery(subapp_world: &mut World, main_world: &mut World, j
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn apply_pass(subapp_world: &mut World, main_world: &mut World, mut new_app: WorldSwapApp)
+fn apply_pass(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    origin: SwapOrigin,
+    behavior: SwapBehavior,
+    mut new_app: WorldSwapApp,
+) -> bool
 {
+    if let Err(reason) = validate_incoming_app(subapp_world, main_world, &new_app) {
+        reject_incoming_app(subapp_world, main_world, origin, SwapCommandKind::Pass, new_app, reason);
+        return false;
+    }
+    detect_stale_app_exit(subapp_world, main_world, origin, SwapCommandKind::Pass, &new_app);
+
+    // Seed the new world before it runs.
+    install_world_rng(main_world, &mut new_app.world);
+
+    // Warm up the new world before it appears.
+    run_warmup_ticks(subapp_world, &mut new_app);
+
     tracing::info!("foreground control passed from {:?} to {:?}; recovering or dropping {:?}",
-        main_world.id(), new_app.world.id(), main_world.id());
+        WorldName::of(main_world), WorldName::of(&new_app.world), WorldName::of(main_world));
 
     // Prepare the new world.
-    prepare_world_swap(subapp_world, main_world, &mut new_app.world);
+    prepare_world_swap(subapp_world, main_world, &mut new_app.world, behavior);
 
     // Force-render the foreground after removing windows.
-    extract_main_world_render_app(subapp_world, main_world);
+    if behavior.render_final_frame {
+        extract_main_world_render_app(subapp_world, main_world);
+    }
 
     // Swap the previous world for the new world.
     let prev_app = swap_worlds(subapp_world, main_world, new_app);
 
     // The previous world is passed to the swap-pass-recovery callback, otherwise dropped.
     handle_swap_pass_recovery(subapp_world, main_world, prev_app);
+
+    true
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn apply_fork(subapp_world: &mut World, main_world: &mut World, mut new_app: WorldSwapApp)
+fn apply_fork(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    origin: SwapOrigin,
+    behavior: SwapBehavior,
+    mut new_app: WorldSwapApp,
+) -> bool
 {
     if subapp_world.non_send_resource::<BackgroundApp>().app.is_some() {
-        panic!("SwapCommand::Fork is not allowed when there is already a world in the background");
+        let reason = SwapErrorReason::BackgroundOccupied;
+        reject_incoming_app(subapp_world, main_world, origin, SwapCommandKind::Fork, new_app, reason);
+        return false;
+    }
+    if let Err(reason) = validate_incoming_app(subapp_world, main_world, &new_app) {
+        reject_incoming_app(subapp_world, main_world, origin, SwapCommandKind::Fork, new_app, reason);
+        return false;
     }
+    detect_stale_app_exit(subapp_world, main_world, origin, SwapCommandKind::Fork, &new_app);
+
+    // Snapshot the new world's resources before it runs.
+    #[cfg(feature = "world_diff")]
+    take_fork_snapshot(&mut new_app.world);
+
+    // Seed the new world before it runs.
+    install_world_rng(main_world, &mut new_app.world);
+
+    // Warm up the new world before it appears.
+    run_warmup_ticks(subapp_world, &mut new_app);
 
     tracing::info!("{:?} forked, now {:?} is foreground and {:?} is background",
-        main_world.id(), new_app.world.id(), main_world.id());
+        WorldName::of(main_world), WorldName::of(&new_app.world), WorldName::of(main_world));
 
     // Prepare the new world.
-    prepare_world_swap(subapp_world, main_world, &mut new_app.world);
+    prepare_world_swap(subapp_world, main_world, &mut new_app.world, behavior);
 
     // Force-render the foreground after removing windows.
-    extract_main_world_render_app(subapp_world, main_world);
+    if behavior.render_final_frame {
+        extract_main_world_render_app(subapp_world, main_world);
+    }
 
     // Swap the previous world for the new world.
     let prev_app = swap_worlds(subapp_world, main_world, new_app);
 
     // Put the previous world in the background.
     add_app_to_background(subapp_world, prev_app);
+
+    true
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn apply_swap(subapp_world: &mut World, main_world: &mut World)
+fn apply_swap(subapp_world: &mut World, main_world: &mut World, origin: SwapOrigin, behavior: SwapBehavior)
+    -> bool
 {
-    if subapp_world.non_send_resource::<BackgroundApp>().app.is_none() {
-        panic!("SwapCommand::Swap is only allowed when there is a world in the background");
+    #[cfg(feature = "winit")]
+    if !validate_background_windows(subapp_world, main_world, origin, SwapCommandKind::Swap) {
+        return false;
     }
-
-    let mut background_app = take_background_app(subapp_world).unwrap();
+    let Some(mut background_app) = take_background_app(subapp_world) else {
+        tracing::error!("discarding Swap command {:?}: {}", origin.id, SwapErrorReason::NoBackgroundWorld);
+        let reason = SwapErrorReason::NoBackgroundWorld;
+        send_swap_error(subapp_world, main_world, origin, SwapCommandKind::Swap, reason);
+        return false;
+    };
     tracing::info!("{:?} swapped, now {:?} is foreground and {:?} is background",
-        main_world.id(), background_app.world.id(), main_world.id());
+        WorldName::of(main_world), WorldName::of(&background_app.world), WorldName::of(main_world));
 
     // Prepare the background world for entering the foreground.
-    prepare_world_swap(subapp_world, main_world, &mut background_app.world);
+    prepare_world_swap(subapp_world, main_world, &mut background_app.world, behavior);
 
     // Force-render the foreground after removing windows.
-    extract_main_world_render_app(subapp_world, main_world);
+    if behavior.render_final_frame {
+        extract_main_world_render_app(subapp_world, main_world);
+    }
 
     // Swap the previous world for the background world.
     let prev_app = swap_worlds(subapp_world, main_world, background_app);
 
     // Put the previous world in the background.
     add_app_to_background(subapp_world, prev_app);
+
+    true
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn apply_join(subapp_world: &mut World, main_world: &mut World)
+fn apply_join(subapp_world: &mut World, main_world: &mut World, origin: SwapOrigin, behavior: SwapBehavior)
+    -> bool
 {
+    #[cfg(feature = "winit")]
+    if !validate_background_windows(subapp_world, main_world, origin, SwapCommandKind::Join) {
+        return false;
+    }
     let Some(mut background_app) = take_background_app(subapp_world) else {
-        panic!("SwapCommand::Join is only allowed when there is a world in the background");
+        tracing::error!("discarding Join command {:?}: {}", origin.id, SwapErrorReason::NoBackgroundWorld);
+        let reason = SwapErrorReason::NoBackgroundWorld;
+        send_swap_error(subapp_world, main_world, origin, SwapCommandKind::Join, reason);
+        return false;
     };
     tracing::info!("{:?} joined, now {:?} is foreground; recovering or dropping {:?}",
-        main_world.id(), background_app.world.id(), main_world.id());
+        WorldName::of(main_world), WorldName::of(&background_app.world), WorldName::of(main_world));
 
     // Prepare the background world for entering the foreground..
-    prepare_world_swap(subapp_world, main_world, &mut background_app.world);
+    prepare_world_swap(subapp_world, main_world, &mut background_app.world, behavior);
 
     // Force-render the foreground after removing windows.
-    extract_main_world_render_app(subapp_world, main_world);
+    if behavior.render_final_frame {
+        extract_main_world_render_app(subapp_world, main_world);
+    }
 
     // Swap the previous world for the background world.
     let prev_app = swap_worlds(subapp_world, main_world, background_app);
 
     // The previous world is passed to the swap-join-recovery callback, otherwise dropped.
     handle_swap_join_recovery(subapp_world, main_world, prev_app);
+
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs the background world's `Main` schedule, even if its clock is paused.
+///
+/// Each step advances time by `delta`, or by the main world's frame time if `delta` is `None`.
+fn run_background_steps(main_world: &World, background_app: &mut WorldSwapApp, steps: u32, delta: Option<Duration>)
+{
+    // Unpause the world's clock and let it advance by the full delta.
+    // - The world's time strategy and fixed timestep are overwritten while stepping, so they are restored after.
+    let paused = background_app.world.resource::<Time<Virtual>>().is_paused();
+    let max_delta = background_app.world.resource::<Time<Virtual>>().max_delta();
+    let strategy = background_app.world.remove_resource::<TimeUpdateStrategy>();
+    let timestep = background_app.world.resource::<Time<Fixed>>().timestep();
+    {
+        let mut time = background_app.world.resource_mut::<Time<Virtual>>();
+        if paused {
+            time.unpause();
+        }
+        if let Some(delta) = delta {
+            time.set_max_delta(max_delta.max(delta));
+        }
+    }
+
+    for _ in 0..steps {
+        sync_lockstep_time(main_world, &mut background_app.world);
+        if let Some(delta) = delta {
+            background_app.world.insert_resource(TimeUpdateStrategy::ManualDuration(delta));
+        }
+        run_background_main(background_app);
+    }
+
+    // Restore the world's clock.
+    {
+        let mut time = background_app.world.resource_mut::<Time<Virtual>>();
+        if paused {
+            time.pause();
+        }
+        time.set_max_delta(max_delta);
+    }
+    background_app.world.resource_mut::<Time<Fixed>>().set_timestep(timestep);
+    match strategy {
+        Some(strategy) => background_app.world.insert_resource(strategy),
+        None => {
+            background_app.world.remove_resource::<TimeUpdateStrategy>();
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_step_background(subapp_world: &mut World, main_world: &mut World, origin: SwapOrigin, steps: u32) -> bool
+{
+    if subapp_world.non_send_resource::<BackgroundApp>().app.is_none() {
+        let reason = SwapErrorReason::NoBackgroundWorld;
+        tracing::error!("discarding StepBackground command {:?}: {}", origin.id, reason);
+        send_swap_error(subapp_world, main_world, origin, SwapCommandKind::StepBackground, reason);
+        return false;
+    }
+    let mut background = subapp_world.non_send_resource_mut::<BackgroundApp>();
+    let background_app = background.app.as_mut().unwrap();
+    tracing::debug!("stepping {:?} {steps} times", WorldName::of(&background_app.world));
+
+    // Advance time by the main world's frame time on each step.
+    run_background_steps(main_world, background_app, steps, None);
+
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_advance_background(
+    subapp_world: &mut World,
+    main_world: &mut World,
+    origin: SwapOrigin,
+    delta: Duration,
+) -> bool
+{
+    if subapp_world.non_send_resource::<BackgroundApp>().app.is_none() {
+        let reason = SwapErrorReason::NoBackgroundWorld;
+        tracing::error!("discarding AdvanceBackground command {:?}: {}", origin.id, reason);
+        send_swap_error(subapp_world, main_world, origin, SwapCommandKind::AdvanceBackground, reason);
+        return false;
+    }
+    let mut background = subapp_world.non_send_resource_mut::<BackgroundApp>();
+    let background_app = background.app.as_mut().unwrap();
+    tracing::debug!("advancing {:?} by {delta:?}", WorldName::of(&background_app.world));
+
+    // Advance time by the full delta in one update.
+    // - Fixed-timestep schedules will catch up in that update.
+    run_background_steps(main_world, background_app, 1, Some(delta));
+
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_abort_all(subapp_world: &mut World, main_world: &mut World, exit: AppExit) -> bool
+{
+    tracing::info!("{:?} aborted all worlds with {:?}", WorldName::of(main_world), exit);
+
+    // The background world is passed to the abort-recovery callback, otherwise dropped.
+    if let Some(background_app) = take_background_app(subapp_world) {
+        if let Some(recovery_fn) = subapp_world.resource::<WorldSwapPlugin>().abort_recovery {
+            (recovery_fn)(main_world, background_app);
+        }
+    }
+
+    // Shut down the app.
+    main_world.send_event(exit.clone());
+    subapp_world.insert_resource(WorldSwapSubAppState::Exiting(exit));
+
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Receives swap commands and selects the one to apply this tick according to [`CommandArbitration`].
+fn receive_swap_request(subapp_world: &mut World, main_world: &mut World) -> Option<SwapRequest>
+{
+    // Report commands that were dropped because the channel was full.
+    while let Ok(dropped) = subapp_world.resource::<DroppedSwapCommandReceiver>().try_recv() {
+        let DroppedSwapCommand { origin, kind } = dropped;
+        send_swap_error(subapp_world, main_world, origin, kind, SwapErrorReason::ChannelFull);
+    }
+
+    let mut requests = std::mem::take(&mut subapp_world.non_send_resource_mut::<QueuedSwapRequests>().requests);
+    while let Ok(request) = subapp_world.resource::<SwapCommandReceiver>().try_recv() {
+        requests.push_back(request);
+    }
+
+    // Custom commands don't swap worlds, so they skip arbitration and are all applied this tick.
+    let (custom, mut requests): (VecDeque<_>, VecDeque<_>) = requests
+        .into_iter()
+        .partition(|request| request.command.kind() == SwapCommandKind::Custom);
+    subapp_world.non_send_resource_mut::<QueuedSwapRequests>().custom.extend(custom);
+    if requests.is_empty() {
+        return None;
+    }
+
+    let background_id = subapp_world
+        .non_send_resource::<BackgroundApp>()
+        .app
+        .as_ref()
+        .map(|app| app.world.id());
+    let from_background =
+        |request: &SwapRequest| request.origin.source.is_some() && request.origin.source == background_id;
+
+    let index = match subapp_world.resource::<WorldSwapPlugin>().command_arbitration {
+        CommandArbitration::ForegroundWins => requests.iter().rposition(|r| !from_background(r)),
+        CommandArbitration::BackgroundWins => requests.iter().rposition(from_background),
+        CommandArbitration::FirstWins => Some(0),
+        CommandArbitration::QueueAll => {
+            let next = requests.pop_front();
+            subapp_world.non_send_resource_mut::<QueuedSwapRequests>().requests = requests;
+            return next;
+        }
+    };
+    let index = index.unwrap_or(requests.len() - 1);
+    let selected = requests.remove(index)?;
+
+    // Handle the other commands.
+    let policy = subapp_world.resource::<WorldSwapPlugin>().discard_policy;
+    if policy == DiscardPolicy::Queue {
+        subapp_world.non_send_resource_mut::<QueuedSwapRequests>().requests = requests;
+        return Some(selected);
+    }
+    for request in requests {
+        match policy {
+            DiscardPolicy::Warn => {
+                tracing::warn!("discarding extra swap command {:?}", request.origin.id);
+                continue;
+            }
+            DiscardPolicy::Silent => continue,
+            DiscardPolicy::ErrorEvent | DiscardPolicy::Queue => (),
+        }
+        let (kind, reason) = (request.command.kind(), SwapErrorReason::Superseded);
+        send_swap_error(subapp_world, main_world, request.origin, kind, reason);
+    }
+
+    Some(selected)
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
 pub(crate) struct ForegroundApp
 {
+    #[cfg(feature = "render")]
     pub(crate) render_app: Option<SubApp>,
     pub(crate) background_tick_rate: Option<BackgroundTickRate>,
     pub(crate) time_sender: Option<TimeSender>,
 }
 
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sub apps run by the backend after swaps are applied. See [`WorldSwapPlugin::ordered_subapps`].
+pub(crate) struct OrderedSubApps
+{
+    pub(crate) subapps: Vec<SubApp>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks frame timing for [`WorldSwapFramePolicy`].
+#[derive(Resource, Default)]
+pub(crate) struct FramePacing
+{
+    /// When the current tick's foreground update started.
+    frame_start: Option<Instant>,
+}
+
+impl FramePacing
+{
+    /// Gets the time since the current tick's foreground update started.
+    pub(crate) fn foreground_elapsed(&self) -> Duration
+    {
+        self.frame_start.map(|frame_start| frame_start.elapsed()).unwrap_or_default()
+    }
+
+    fn has_background_headroom(&self, policy: WorldSwapFramePolicy) -> bool
+    {
+        let (Some(frame_time), Some(frame_start)) = (policy.min_frame_time, self.frame_start) else { return true };
+        frame_time.saturating_sub(frame_start.elapsed()) >= policy.background_headroom
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Marks the foreground world while commands are waiting in [`QueuedSwapRequests`].
+///
+/// See [`no_swap_pending`].
+#[derive(Resource, Default)]
+pub(crate) struct QueuedSwapMarker;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Swap commands waiting to be applied with [`CommandArbitration::QueueAll`] or [`DiscardPolicy::Queue`].
+#[derive(Default)]
+pub(crate) struct QueuedSwapRequests
+{
+    pub(crate) requests: VecDeque<SwapRequest>,
+    /// [`SwapCommand::Custom`] commands received this tick, which bypass command arbitration.
+    pub(crate) custom: Vec<SwapRequest>,
+}
+
+
 //-------------------------------------------------------------------------------------------------------------------
 
 pub(crate) struct BackgroundApp
@@ -548,11 +1667,12 @@ pub(crate) struct BackgroundApp
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Resource, Copy, Clone, Eq, PartialEq)]
+#[derive(Resource, Clone, Eq, PartialEq)]
 pub(crate) enum WorldSwapSubAppState
 {
     Running,
-    Exiting,
+    /// Contains the `AppExit` that shut down the app.
+    Exiting(AppExit),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -561,40 +1681,122 @@ pub(crate) enum WorldSwapSubAppState
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
 pub(crate) struct WorldSwapSubApp;
 
-pub(crate) fn world_swap_extract(main_world: &mut World, subapp_world: &mut World)
-{
-    // Intercept AppExit events from the main world and convert them to SwapCommand::Join commands if possible.
-    // - We do this here instead of as a system in the world to ensure *all* AppExit events are captured.
-    intercept_app_exit(subapp_world, main_world);
+//-------------------------------------------------------------------------------------------------------------------
 
-    // Get any commands sent by the main world.
-    let mut swap_command = None;
-    while let Ok(new_swap_command) = subapp_world.resource::<SwapCommandReceiver>().try_recv() {
-        if swap_command.is_some() {
-            tracing::warn!("discarding extra swap command");
+/// Applies a single command.
+///
+/// Returns `true` if worlds were swapped.
+fn apply_swap_command(subapp_world: &mut World, main_world: &mut World, swap_request: SwapRequest) -> bool
+{
+    let SwapRequest { origin, command, options } = swap_request;
+    let kind = command.kind();
+    let behavior = options.resolve(subapp_world.resource::<WorldSwapPlugin>().swap_behavior);
+    tracing::debug!("applying {:?} command {:?}", kind, origin.id);
+    #[cfg(feature = "metrics")]
+    subapp_world.insert_resource(CommandTimer(Instant::now()));
+    let swapped = match command {
+        SwapCommand::Pass(new_app) => apply_pass(subapp_world, main_world, origin, behavior, new_app),
+        SwapCommand::Fork(new_app) => apply_fork(subapp_world, main_world, origin, behavior, new_app),
+        SwapCommand::Swap => apply_swap(subapp_world, main_world, origin, behavior),
+        SwapCommand::Join => apply_join(subapp_world, main_world, origin, behavior),
+        SwapCommand::AbortAll(exit) => apply_abort_all(subapp_world, main_world, exit),
+        SwapCommand::StepBackground(steps) => {
+            // Stepping doesn't swap worlds, so it's reported here.
+            if apply_step_background(subapp_world, main_world, origin, steps) {
+                send_swap_applied(subapp_world, main_world, origin, kind);
+            }
+            false
         }
-        swap_command = Some(new_swap_command);
+        SwapCommand::AdvanceBackground(delta) => {
+            // Advancing doesn't swap worlds, so it's reported here.
+            if apply_advance_background(subapp_world, main_world, origin, delta) {
+                send_swap_applied(subapp_world, main_world, origin, kind);
+            }
+            false
+        }
+        SwapCommand::Custom(callback) => {
+            // Custom callbacks must not swap worlds, so they are reported here.
+            let result = {
+                let mut background = subapp_world.non_send_resource_mut::<BackgroundApp>();
+                let background = background.app.as_mut().map(|background_app| &mut background_app.world);
+                (callback)(CustomSwapWorlds { foreground: main_world, background })
+            };
+            match result {
+                Ok(()) => send_swap_applied(subapp_world, main_world, origin, kind),
+                Err(reason) => {
+                    tracing::error!("discarding Custom command {:?}: {reason}", origin.id);
+                    send_swap_error(subapp_world, main_world, origin, kind, reason);
+                }
+            }
+            false
+        }
+    };
+    if swapped {
+        send_swap_applied(subapp_world, main_world, origin, kind);
+    }
+    swapped
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Applies the [`SwapCommand::Custom`] commands and the arbitrated command received this tick, if any.
+///
+/// Returns `true` if worlds were swapped.
+fn apply_swap_request(subapp_world: &mut World, main_world: &mut World, swap_request: Option<SwapRequest>) -> bool
+{
+    // Apply custom commands in the order they were received.
+    // - These run before the arbitrated command so they see the worlds that were in place when they were sent.
+    let custom = std::mem::take(&mut subapp_world.non_send_resource_mut::<QueuedSwapRequests>().custom);
+    for request in custom {
+        apply_swap_command(subapp_world, main_world, request);
     }
 
-    // Apply the most recent SwapCommand.
+    // Apply the SwapCommand.
     // - This will force-render the foreground world after removing windows, which ensures the foreground world
     // is 'fully updated' in case it expects a strict 'update - extract' sequence. We don't display the foreground
     // world's last frame (i.e. we render after removing windows) because it may contain visual effects of the swap
     // (e.g. button/state changes) that should only be shown after swapping back.
-    let swapped = swap_command.is_some();
-    if let Some(swap_command) = swap_command {
-        match swap_command {
-            SwapCommand::Pass(new_app) => apply_pass(subapp_world, main_world, new_app),
-            SwapCommand::Fork(new_app) => apply_fork(subapp_world, main_world, new_app),
-            SwapCommand::Swap => apply_swap(subapp_world, main_world),
-            SwapCommand::Join => apply_join(subapp_world, main_world),
-        }
+    // - Commands that fail are discarded and reported with a SwapError event to the world that sent them.
+    let swapped = match swap_request {
+        Some(swap_request) => apply_swap_command(subapp_world, main_world, swap_request),
+        None => false,
+    };
+
+    // Deliver attention requests to the foreground world.
+    // - We do this after applying swaps so requests reach the world that is now in the foreground.
+    forward_attention_requests(subapp_world, main_world);
+
+    // Replay window events that were deferred when the main world entered the foreground.
+    // - This runs on the tick after a swap, once the main world has updated in the foreground.
+    #[cfg(feature = "winit")]
+    if !swapped {
+        replay_deferred_window_events(main_world);
     }
 
+    // Run debug inspections of the background world.
+    #[cfg(feature = "inspect")]
+    run_background_inspections(subapp_world);
+
+    swapped
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Renders the foreground world and runs ordered sub apps.
+///
+/// Nothing is rendered on ticks where worlds are swapped.
+fn render_foreground(subapp_world: &mut World, main_world: &mut World, swapped: bool)
+{
+    // Request screenshots of the foreground world.
+    // - This must be done before extracting so the request reaches the render app.
+    #[cfg(all(feature = "render", feature = "winit"))]
+    capture_frame(subapp_world, main_world, swapped);
+
     // Extract the main world into its rendering subapp.
     // - We do NOT extract if we are waiting for a pipelined RenderApp from a previous world to finish its current
     //   job.
-    if !swapped && can_render(subapp_world, main_world) {
+    let extract = !swapped && can_render(subapp_world, main_world);
+    if extract {
         extract_main_world_render_app(subapp_world, main_world);
     } else if !swapped {
         // If we didn't extract, then we need to send time manually to the main world otherwise Bevy logs a
@@ -605,22 +1807,93 @@ pub(crate) fn world_swap_extract(main_world: &mut World, subapp_world: &mut Worl
         send_time_to_main_world(subapp_world);
     }
 
-    // If we swapped this tick, then skip the background update since the background world was just updated in the
-    // foreground.
-    if swapped {
-        return;
+    #[cfg(feature = "render")]
+    update_render_health(subapp_world, main_world, extract);
+
+    // Run sub apps that depend on a fixed order relative to swaps.
+    if !swapped {
+        run_ordered_subapps(subapp_world, main_world);
     }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Updates the background world, and shuts down the app if the background world exited.
+fn update_background(subapp_world: &mut World, main_world: &mut World)
+{
+    record_profile_background(subapp_world);
+    let start = Instant::now();
+    let exit = update_background_world(subapp_world, main_world);
+    record_profile_stage(subapp_world, ProfileStage::BackgroundMain, start.elapsed());
+
+    if let Some(exit) = exit {
+        main_world.send_event(exit.clone());
+        subapp_world.insert_resource(WorldSwapSubAppState::Exiting(exit));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs the backend's tick after the foreground world's `Main` schedule.
+///
+/// The tick is a pipeline of [`SwapStages`](SwapStage). [`SwapMiddleware`] runs before and after each stage.
+pub(crate) fn world_swap_extract(main_world: &mut World, subapp_world: &mut World)
+{
+    // Start profiling the tick.
+    // - We do this first so the foreground world's time is attributed to the world that ran Main.
+    begin_frame_profile(subapp_world, main_world);
+
+    // Intercept AppExit events from the main world and convert them to SwapCommand::Join commands if possible.
+    // - We do this here instead of as a system in the world to ensure *all* AppExit events are captured.
+    run_swap_middleware(subapp_world, main_world, SwapStage::InterceptExit, StagePosition::Before);
+    intercept_app_exit(subapp_world, main_world);
+    run_swap_middleware(subapp_world, main_world, SwapStage::InterceptExit, StagePosition::After);
+
+    // Get the command to apply this tick.
+    run_swap_middleware(subapp_world, main_world, SwapStage::ReceiveCommands, StagePosition::Before);
+    let swap_request = receive_swap_request(subapp_world, main_world);
+    run_swap_middleware(subapp_world, main_world, SwapStage::ReceiveCommands, StagePosition::After);
+
+    // Apply the command.
+    run_swap_middleware(subapp_world, main_world, SwapStage::ApplySwap, StagePosition::Before);
+    let swapped = apply_swap_request(subapp_world, main_world, swap_request);
+    run_swap_middleware(subapp_world, main_world, SwapStage::ApplySwap, StagePosition::After);
+
+    // Render the foreground world.
+    run_swap_middleware(subapp_world, main_world, SwapStage::Render, StagePosition::Before);
+    render_foreground(subapp_world, main_world, swapped);
+    run_swap_middleware(subapp_world, main_world, SwapStage::Render, StagePosition::After);
 
     // Update the background world.
     // - Do this last so rendering the foreground world is scheduled as soon as possible.
     // - Note that any SwapCommands sent by the background world will go to the beginning of the command queue, so
-    // foreground commands will take precedence.
-    let should_exit = update_background_world(subapp_world);
+    // command arbitration will see them before foreground commands.
+    // - If we swapped this tick, then skip the background update since the background world was just updated in
+    // the foreground.
+    // - The registry is also synced on the first tick, so the initial world is registered before any swaps.
+    if swapped || !subapp_world.contains_resource::<WorldRegistrySynced>() {
+        sync_world_registry(subapp_world, main_world);
+    }
+    if !swapped {
+        run_swap_middleware(subapp_world, main_world, SwapStage::BackgroundUpdate, StagePosition::Before);
+        update_background(subapp_world, main_world);
+        run_swap_middleware(subapp_world, main_world, SwapStage::BackgroundUpdate, StagePosition::After);
+    }
 
-    if should_exit {
-        main_world.send_event(AppExit::Success);
-        subapp_world.insert_resource(WorldSwapSubAppState::Exiting);
+    // Tell the main world if queued commands will be applied after its next update.
+    if subapp_world.non_send_resource::<QueuedSwapRequests>().requests.is_empty() {
+        main_world.remove_resource::<QueuedSwapMarker>();
+    } else if !main_world.contains_resource::<QueuedSwapMarker>() {
+        main_world.init_resource::<QueuedSwapMarker>();
     }
-}
 
+    // The main world's Main schedule will run next.
+    set_panic_context(main_world, Main);
+
+    // Report profiling results to the foreground world.
+    finish_frame_profile(subapp_world, main_world);
+
+    // Wait out the rest of the frame if the loop rate is capped.
+    pace_frame(subapp_world);
+}
 //-------------------------------------------------------------------------------------------------------------------
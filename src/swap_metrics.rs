@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::utils::Instant;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource in the worldswap subapp that records when the backend started applying the current swap command.
+#[derive(Resource)]
+pub(crate) struct CommandTimer(pub(crate) Instant);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records a swap command that was applied, and how long it took to apply.
+///
+/// Emits `worldswap_commands_applied_total` and `worldswap_command_duration_seconds`, labeled by `command`.
+pub(crate) fn record_command_applied(command: SwapCommandKind, duration: Duration)
+{
+    let command = format!("{command:?}");
+    ::metrics::counter!("worldswap_commands_applied_total", "command" => command.clone()).increment(1);
+    ::metrics::histogram!("worldswap_command_duration_seconds", "command" => command)
+        .record(duration.as_secs_f64());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records a swap command that failed.
+///
+/// Emits `worldswap_command_errors_total`, labeled by `command` and `reason`.
+pub(crate) fn record_command_error(command: SwapCommandKind, reason: SwapErrorReason)
+{
+    ::metrics::counter!(
+        "worldswap_command_errors_total",
+        "command" => format!("{command:?}"),
+        "reason" => format!("{reason:?}"),
+    )
+    .increment(1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records an update of the background world.
+///
+/// Emits `worldswap_background_ticks_total` and `worldswap_background_tick_duration_seconds`.
+pub(crate) fn record_background_tick(duration: Duration)
+{
+    ::metrics::counter!("worldswap_background_ticks_total").increment(1);
+    ::metrics::histogram!("worldswap_background_tick_duration_seconds").record(duration.as_secs_f64());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
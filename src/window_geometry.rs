@@ -0,0 +1,179 @@
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowPosition};
+use bevy::winit::WinitWindows;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn primary_window_entity(world: &mut World) -> Option<Entity>
+{
+    world
+        .query_filtered::<Entity, (With<Window>, With<PrimaryWindow>)>()
+        .iter(world)
+        .next()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records the geometry of the outgoing world's primary window in its [`WindowGeometryProfile`].
+pub(crate) fn record_window_geometry(world: &mut World)
+{
+    if !world.contains_resource::<WindowGeometryProfile>() {
+        return;
+    }
+    let Some(entity) = primary_window_entity(world) else { return };
+    let Some(window) = world.get::<Window>(entity) else { return };
+
+    let maximized = world
+        .get_non_send_resource::<WinitWindows>()
+        .and_then(|windows| windows.get_window(entity))
+        .map(|window| window.is_maximized())
+        .unwrap_or(false);
+    let geometry = WindowGeometry {
+        size: Vec2::new(window.resolution.width(), window.resolution.height()),
+        position: match window.position {
+            WindowPosition::At(position) => Some(position),
+            _ => None,
+        },
+        maximized,
+    };
+
+    let mut profile = world.resource_mut::<WindowGeometryProfile>();
+    profile.geometry = Some(geometry);
+    if let Err(err) = profile.save() {
+        tracing::warn!("failed saving window geometry profile: {err}");
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Applies the incoming world's [`WindowGeometryProfile`] to its primary window.
+pub(crate) fn apply_window_geometry(world: &mut World)
+{
+    let Some(geometry) = world.get_resource::<WindowGeometryProfile>().and_then(|p| p.geometry) else { return };
+    let Some(entity) = primary_window_entity(world) else { return };
+    let Some(mut window) = world.get_mut::<Window>(entity) else { return };
+
+    window.resolution.set(geometry.size.x, geometry.size.y);
+    if let Some(position) = geometry.position {
+        window.position = WindowPosition::At(position);
+    }
+    window.set_maximized(geometry.maximized);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The size, position, and maximized state of a window.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WindowGeometry
+{
+    /// Logical size of the window.
+    pub size: Vec2,
+    /// Physical position of the window, if known.
+    pub position: Option<IVec2>,
+    /// Whether the window is maximized.
+    pub maximized: bool,
+}
+
+impl WindowGeometry
+{
+    fn encode(&self) -> String
+    {
+        let (x, y) = match self.position {
+            Some(position) => (position.x.to_string(), position.y.to_string()),
+            None => ("-".into(), "-".into()),
+        };
+        format!("{} {} {} {} {}", self.size.x, self.size.y, x, y, self.maximized)
+    }
+
+    fn decode(data: &str) -> Option<Self>
+    {
+        let mut parts = data.split_whitespace();
+        let width = parts.next()?.parse().ok()?;
+        let height = parts.next()?.parse().ok()?;
+        let position = match (parts.next()?, parts.next()?) {
+            ("-", "-") => None,
+            (x, y) => Some(IVec2::new(x.parse().ok()?, y.parse().ok()?)),
+        };
+        let maximized = parts.next()?.parse().ok()?;
+        Some(Self { size: Vec2::new(width, height), position, maximized })
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that stores a world's preferred primary window geometry.
+///
+/// When a world with this resource leaves the foreground, its primary window's geometry is recorded. When it
+/// enters the foreground, the recorded geometry is applied to the primary window. This lets worlds keep their own
+/// window layouts (e.g. an editor world and a game world).
+///
+/// If the profile has a file, then it is loaded when the profile is created and saved whenever geometry is
+/// recorded.
+///
+/// Use [`WindowGeometryPlugin`] to add a profile to a world.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct WindowGeometryProfile
+{
+    /// The recorded geometry.
+    pub geometry: Option<WindowGeometry>,
+    path: Option<PathBuf>,
+}
+
+impl WindowGeometryProfile
+{
+    /// Makes a profile that is only stored in memory.
+    pub fn in_memory() -> Self
+    {
+        Self::default()
+    }
+
+    /// Makes a profile that is persisted to a file.
+    ///
+    /// Existing geometry is loaded from the file if possible.
+    pub fn from_file(path: impl AsRef<Path>) -> Self
+    {
+        let path = path.as_ref().to_path_buf();
+        let geometry = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| WindowGeometry::decode(&data));
+        Self { geometry, path: Some(path) }
+    }
+
+    /// Saves the profile to its file.
+    ///
+    /// Does nothing for in-memory profiles or profiles without geometry.
+    pub fn save(&self) -> std::io::Result<()>
+    {
+        let (Some(path), Some(geometry)) = (&self.path, &self.geometry) else { return Ok(()) };
+        std::fs::write(path, geometry.encode())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that adds a [`WindowGeometryProfile`] to a world.
+pub struct WindowGeometryPlugin
+{
+    profile: WindowGeometryProfile,
+}
+
+impl WindowGeometryPlugin
+{
+    /// Makes a plugin with the given profile.
+    pub fn new(profile: WindowGeometryProfile) -> Self
+    {
+        Self { profile }
+    }
+}
+
+impl Plugin for WindowGeometryPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.insert_resource(self.profile.clone());
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,7 +1,16 @@
-use bevy::ecs::entity::EntityHashMap;
+use std::mem::Discriminant;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bevy::ecs::component::Tick;
+use bevy::ecs::world::WorldId;
 use bevy::prelude::*;
-use bevy::window::{WindowBackendScaleFactorChanged, WindowScaleFactorChanged, WindowThemeChanged};
-use bevy::winit::{WinitEvent, WinitWindows};
+use bevy::utils::HashMap;
+use bevy::window::{
+    PrimaryWindow, RawHandleWrapper, WindowBackendScaleFactorChanged, WindowCloseRequested, WindowFocused,
+    WindowMoved, WindowOccluded, WindowResized, WindowScaleFactorChanged, WindowThemeChanged,
+};
+use bevy::winit::{CachedWindow, WinitEvent, WinitWindows};
+use winit::window::WindowId;
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -17,86 +26,498 @@ pub(crate) fn map_winit_window_entities(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Records when a window entity's `Window` component was copied from another world.
+///
+/// Used to skip copying `Window` components that are already equal in both worlds.
+#[derive(Component, Copy, Clone)]
+pub(crate) struct WindowSync
+{
+    /// The world the component was copied from.
+    source: WorldId,
+    /// When the source world's component last changed, at the time of the copy.
+    source_tick: Tick,
+    /// When this world's component last changed, right after the copy.
+    local_tick: Tick,
+}
+
+fn window_changed(world: &World, entity: Entity) -> Option<Tick>
+{
+    Some(world.get_entity(entity)?.get_ref::<Window>()?.last_changed())
+}
+
+/// Returns `true` if two window entities in different worlds have equal `Window` components because neither was
+/// changed since one was copied from the other.
+pub(crate) fn windows_in_sync(world_a: &World, entity_a: Entity, world_b: &World, entity_b: Entity) -> bool
+{
+    let (Some(changed_a), Some(changed_b)) = (window_changed(world_a, entity_a), window_changed(world_b, entity_b))
+    else {
+        return false;
+    };
+    let copied = |sync: Option<&WindowSync>, source: WorldId, source_tick: Tick, local_tick: Tick| {
+        sync.is_some_and(|sync| {
+            sync.source == source && sync.source_tick == source_tick && sync.local_tick == local_tick
+        })
+    };
+
+    copied(world_b.get::<WindowSync>(entity_b), world_a.id(), changed_a, changed_b)
+        || copied(world_a.get::<WindowSync>(entity_a), world_b.id(), changed_b, changed_a)
+}
+
+/// Records that a window entity's `Window` component was just copied from another world.
+pub(crate) fn record_window_sync(source_world: &World, source: Entity, world: &mut World, entity: Entity)
+{
+    let Some(source_tick) = window_changed(source_world, source) else { return };
+    let Some(local_tick) = window_changed(world, entity) else { return };
+    world
+        .entity_mut(entity)
+        .insert(WindowSync { source: source_world.id(), source_tick, local_tick });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Creates the archetypes that window entities move through when windows are transferred into a world.
+///
+/// This way the first swap into a fresh world doesn't pay for creating archetypes. `RawHandleWrapper` can't be
+/// constructed here, so it is only registered.
+///
+/// Archetypes belong to a world, so this spawns and despawns two entities in the world itself. Their entity
+/// indices are reused with a new generation, and removal events in the world are cleared. Only call this on worlds
+/// that haven't run yet.
+pub(crate) fn prewarm_window_archetypes(world: &mut World)
+{
+    world.init_component::<RawHandleWrapper>();
+
+    // Follow the same insertion order as `transfer_windows` for primary and secondary windows.
+    let sync = WindowSync { source: world.id(), source_tick: Tick::new(0), local_tick: Tick::new(0) };
+    for primary in [true, false] {
+        let window = Window::default();
+        let mut entity = world.spawn((window.clone(), CachedWindow { window }));
+        if primary {
+            entity.insert(PrimaryWindow);
+        }
+        entity.insert(sync);
+        entity.despawn();
+    }
+
+    // Flush removal events for the despawned entities so winit doesn't think windows were closed.
+    // - Removal events are double-buffered, so they are only dropped after the second clear.
+    world.clear_trackers();
+    world.clear_trackers();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Callback for reacting to a window entity whose OS window was closed while its world was not in the foreground.
+pub type StaleWindowFn = fn(&mut World, Entity);
+
+/// Resource that lets a world react to windows closed while it was not in the foreground.
+///
+/// When a world enters the foreground, window entities whose OS windows no longer exist are despawned in order of
+/// entity index. The callback is called on each entity right before it is despawned, so the world can react
+/// (e.g. retarget cameras) before its first visible frame. `WindowClosed` events are sent for the despawned
+/// entities when the world next updates.
+///
+/// Note that [`WinitWindows`] is not available in the world while the callback runs.
+#[derive(Resource, Copy, Clone)]
+pub struct StaleWindowHandler(pub StaleWindowFn);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that makes a world reopen windows that were closed while it was not in the foreground.
+///
+/// When the world enters the foreground, window entities whose OS windows no longer exist are kept, and new OS
+/// windows are created for them with the same config when the world next updates. [`StaleWindowHandler`] is not
+/// called for reopened windows.
+#[derive(Resource, Debug, Copy, Clone, Default)]
+pub struct ReopenClosedWindows;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event sent to a world that takes a window after a [`WindowCloseRequested`] event for that window was handled by
+/// another world.
+///
+/// This is historical: the close request was already handled (or ignored) by the world that received it. Worlds
+/// can use it to react to close requests they missed (e.g. saving before the user quits from a menu world).
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeferredWindowCloseRequested
+{
+    /// The window that was requested to close, in the receiving world.
+    pub window: Entity,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The kind of an event stored in a [`WindowEventCache`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CachedWindowEventKind
+{
+    BackendScaleFactorChanged,
+    ScaleFactorChanged,
+    ThemeChanged,
+    Resized,
+    Moved,
+    Focused,
+    Occluded,
+    CloseRequested,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A window lifecycle event cached while its world is not in the foreground.
+#[derive(Clone)]
+enum CachedWindowEvent
+{
+    BackendScaleFactor(WindowBackendScaleFactorChanged),
+    ScaleFactor(WindowScaleFactorChanged),
+    Theme(WindowThemeChanged),
+    Resized(WindowResized),
+    Moved(WindowMoved),
+    Focused(WindowFocused),
+    Occluded(WindowOccluded),
+    CloseRequested(WindowCloseRequested),
+}
+
+impl CachedWindowEvent
+{
+    fn kind(&self) -> CachedWindowEventKind
+    {
+        match self {
+            Self::BackendScaleFactor(_) => CachedWindowEventKind::BackendScaleFactorChanged,
+            Self::ScaleFactor(_) => CachedWindowEventKind::ScaleFactorChanged,
+            Self::Theme(_) => CachedWindowEventKind::ThemeChanged,
+            Self::Resized(_) => CachedWindowEventKind::Resized,
+            Self::Moved(_) => CachedWindowEventKind::Moved,
+            Self::Focused(_) => CachedWindowEventKind::Focused,
+            Self::Occluded(_) => CachedWindowEventKind::Occluded,
+            Self::CloseRequested(_) => CachedWindowEventKind::CloseRequested,
+        }
+    }
+
+    fn set_window(&mut self, window: Entity)
+    {
+        match self {
+            Self::BackendScaleFactor(event) => event.window = window,
+            Self::ScaleFactor(event) => event.window = window,
+            Self::Theme(event) => event.window = window,
+            Self::Resized(event) => event.window = window,
+            Self::Moved(event) => event.window = window,
+            Self::Focused(event) => event.window = window,
+            Self::Occluded(event) => event.window = window,
+            Self::CloseRequested(event) => event.window = window,
+        }
+    }
+
+    fn send(self, world: &mut World)
+    {
+        match self {
+            Self::BackendScaleFactor(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::WindowBackendScaleFactorChanged(event));
+            }
+            Self::ScaleFactor(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::WindowScaleFactorChanged(event));
+            }
+            Self::Theme(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::WindowThemeChanged(event));
+            }
+            Self::Resized(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::WindowResized(event));
+            }
+            Self::Moved(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::WindowMoved(event));
+            }
+            Self::Focused(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::WindowFocused(event));
+            }
+            Self::Occluded(event) => {
+                world.send_event(event.clone());
+                world.send_event(WinitEvent::WindowOccluded(event));
+            }
+            Self::CloseRequested(event) => {
+                world.send_event(DeferredWindowCloseRequested { window: event.window });
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Counter for [`WindowEventStamps`].
+static NEXT_WINDOW_EVENT_STAMP: AtomicU64 = AtomicU64::new(0);
+
+/// Identifies a kind of cached window event for a window.
+type WindowEventKey = (WindowId, Discriminant<CachedWindowEvent>);
+
+/// Records which cached window events a world has seen, so they aren't dispatched to it twice.
+///
+/// Events are stamped when they are first collected. Events dispatched into a world are collected again when that
+/// world updates; those echoes keep their original stamps so they aren't dispatched back to other worlds.
 #[derive(Resource, Default)]
-pub(crate) struct WindowEventCache
+pub(crate) struct WindowEventStamps
 {
-    backend_scale_factor_events: EntityHashMap<WindowBackendScaleFactorChanged>,
-    scale_factor_events: EntityHashMap<WindowScaleFactorChanged>,
-    theme_events: EntityHashMap<WindowThemeChanged>,
+    /// The latest stamp seen for each kind of event on each window.
+    seen: HashMap<WindowEventKey, u64>,
+    /// Stamps of events dispatched into the world that it hasn't collected yet.
+    echoes: HashMap<WindowEventKey, u64>,
+}
+
+impl WindowEventStamps
+{
+    /// Stamps an event collected from the world.
+    fn collect(&mut self, key: WindowEventKey) -> u64
+    {
+        let stamp = self
+            .echoes
+            .remove(&key)
+            .unwrap_or_else(|| NEXT_WINDOW_EVENT_STAMP.fetch_add(1, Ordering::Relaxed));
+        self.seen.insert(key, stamp);
+        stamp
+    }
+
+    /// Returns `false` if the world already saw this event (or a newer event of the same kind).
+    fn receive(&mut self, event: &CachedWindowEvent, window_id: WindowId, stamp: u64) -> bool
+    {
+        let key = (window_id, std::mem::discriminant(event));
+        if self.seen.get(&key).is_some_and(|seen| *seen >= stamp) {
+            return false;
+        }
+        self.seen.insert(key, stamp);
+
+        // Close requests are sent as DeferredWindowCloseRequested, so they won't be collected again.
+        if !matches!(event, CachedWindowEvent::CloseRequested(_)) {
+            self.echoes.insert(key, stamp);
+        }
+        true
+    }
+
+    /// Forgets echoes that weren't collected.
+    ///
+    /// Echoes are collected in the first update after they are dispatched.
+    pub(crate) fn clear_echoes(&mut self)
+    {
+        self.echoes.clear();
+    }
+
+    /// Removes stamps for windows that no longer exist.
+    pub(crate) fn retain_windows(&mut self, windows: &WinitWindows)
+    {
+        self.seen.retain(|(window_id, _), _| windows.winit_to_entity.contains_key(window_id));
+        self.echoes.retain(|(window_id, _), _| windows.winit_to_entity.contains_key(window_id));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Window events waiting to be sent to a world that just entered the foreground.
+///
+/// See [`SwapBehavior::defer_window_event_replay`](crate::SwapBehavior::defer_window_event_replay).
+#[derive(Resource)]
+pub(crate) struct DeferredWindowEventReplay(pub(crate) WindowEventCache);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource with an ordered digest of window lifecycle events.
+///
+/// Events are keyed by winit window id so they can be mapped into any world. Only the latest event of each kind
+/// is kept for each window, and events are ordered by when they last occurred.
+///
+/// While a world is in the foreground, its cache collects events for other worlds. While a world is away from the
+/// foreground, its cache holds events that will be sent to it when it re-enters the foreground (depending on
+/// [`SwapBehavior::replay_window_events`](crate::SwapBehavior::replay_window_events)). Advanced users with custom
+/// window flows can inspect and clear pending events here, e.g. in a [`WorldSwapApp`](crate::WorldSwapApp) before
+/// swapping it in.
+#[derive(Resource, Default, Clone)]
+pub struct WindowEventCache
+{
+    events: Vec<(WindowId, CachedWindowEvent, u64)>,
 }
 
 impl WindowEventCache
 {
-    pub(crate) fn remove(&mut self, entity: Entity)
+    /// Returns the number of cached events.
+    pub fn len(&self) -> usize
     {
-        self.backend_scale_factor_events.remove(&entity);
-        self.scale_factor_events.remove(&entity);
-        self.theme_events.remove(&entity);
+        self.events.len()
     }
 
-    pub(crate) fn insert_backend_scale_factor_event(&mut self, event: WindowBackendScaleFactorChanged)
+    /// Returns `true` if there are no cached events.
+    pub fn is_empty(&self) -> bool
     {
-        self.backend_scale_factor_events.insert(event.window, event);
+        self.events.is_empty()
     }
 
-    pub(crate) fn insert_scale_factor_event(&mut self, event: WindowScaleFactorChanged)
+    /// Iterates cached events from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = (WindowId, CachedWindowEventKind)> + '_
     {
-        self.scale_factor_events.insert(event.window, event);
+        self.events.iter().map(|(window_id, event, _)| (*window_id, event.kind()))
     }
 
-    pub(crate) fn insert_theme_event(&mut self, event: WindowThemeChanged)
+    /// Removes cached events that don't match the predicate.
+    pub fn retain(&mut self, mut predicate: impl FnMut(WindowId, CachedWindowEventKind) -> bool)
     {
-        self.theme_events.insert(event.window, event);
+        self.events.retain(|(window_id, event, _)| predicate(*window_id, event.kind()));
     }
 
-    pub(crate) fn dispatch(
+    /// Removes all cached events for a window.
+    pub fn clear_window(&mut self, window_id: WindowId)
+    {
+        self.retain(|id, _| id != window_id);
+    }
+
+    /// Removes all cached events.
+    pub fn clear(&mut self)
+    {
+        self.events.clear();
+    }
+
+    /// Removes events for windows that no longer exist.
+    pub(crate) fn retain_windows(&mut self, windows: &WinitWindows)
+    {
+        self.events.retain(|(window_id, _, _)| windows.winit_to_entity.contains_key(window_id));
+    }
+
+    fn insert(&mut self, window_id: WindowId, event: CachedWindowEvent, stamp: u64)
+    {
+        // Replace the previous event of the same kind so the digest stays ordered by last occurrence.
+        self.events.retain(|(id, prev, _)| {
+            *id != window_id || std::mem::discriminant(prev) != std::mem::discriminant(&event)
+        });
+        self.events.push((window_id, event, stamp));
+    }
+
+    /// Inserts an event collected from the world that owns this cache.
+    fn record(&mut self, stamps: &mut WindowEventStamps, window_id: WindowId, event: CachedWindowEvent)
+    {
+        let stamp = stamps.collect((window_id, std::mem::discriminant(&event)));
+        self.insert(window_id, event, stamp);
+    }
+
+    pub(crate) fn insert_backend_scale_factor_event(
         &mut self,
-        main_windows: &WinitWindows,
-        new_windows: &WinitWindows,
-        new_world: &mut World,
+        windows: &WinitWindows,
+        stamps: &mut WindowEventStamps,
+        event: WindowBackendScaleFactorChanged,
     )
     {
-        for (entity, mut event) in self.backend_scale_factor_events.drain() {
-            // Drop events that don't have matching entities.
-            let Some(new_world_entity) = map_winit_window_entities(main_windows, new_windows, entity) else {
-                continue;
-            };
+        let Some(window_id) = windows.entity_to_winit.get(&event.window) else { return };
+        self.record(stamps, *window_id, CachedWindowEvent::BackendScaleFactor(event));
+    }
 
-            // Map the event's window.
-            event.window = new_world_entity;
+    pub(crate) fn insert_scale_factor_event(
+        &mut self,
+        windows: &WinitWindows,
+        stamps: &mut WindowEventStamps,
+        event: WindowScaleFactorChanged,
+    )
+    {
+        let Some(window_id) = windows.entity_to_winit.get(&event.window) else { return };
+        self.record(stamps, *window_id, CachedWindowEvent::ScaleFactor(event));
+    }
 
-            // Forward to the new world.
-            new_world.send_event(event.clone());
-            new_world.send_event(WinitEvent::WindowBackendScaleFactorChanged(event));
-        }
+    pub(crate) fn insert_theme_event(
+        &mut self,
+        windows: &WinitWindows,
+        stamps: &mut WindowEventStamps,
+        event: WindowThemeChanged,
+    )
+    {
+        let Some(window_id) = windows.entity_to_winit.get(&event.window) else { return };
+        self.record(stamps, *window_id, CachedWindowEvent::Theme(event));
+    }
 
-        for (entity, mut event) in self.scale_factor_events.drain() {
-            // Drop events that don't have matching entities.
-            let Some(new_world_entity) = map_winit_window_entities(main_windows, new_windows, entity) else {
-                continue;
-            };
+    pub(crate) fn insert_resized_event(
+        &mut self,
+        windows: &WinitWindows,
+        stamps: &mut WindowEventStamps,
+        event: WindowResized,
+    )
+    {
+        let Some(window_id) = windows.entity_to_winit.get(&event.window) else { return };
+        self.record(stamps, *window_id, CachedWindowEvent::Resized(event));
+    }
+
+    pub(crate) fn insert_moved_event(
+        &mut self,
+        windows: &WinitWindows,
+        stamps: &mut WindowEventStamps,
+        event: WindowMoved,
+    )
+    {
+        let Some(window_id) = windows.entity_to_winit.get(&event.window) else { return };
+        self.record(stamps, *window_id, CachedWindowEvent::Moved(event));
+    }
+
+    pub(crate) fn insert_focused_event(
+        &mut self,
+        windows: &WinitWindows,
+        stamps: &mut WindowEventStamps,
+        event: WindowFocused,
+    )
+    {
+        let Some(window_id) = windows.entity_to_winit.get(&event.window) else { return };
+        self.record(stamps, *window_id, CachedWindowEvent::Focused(event));
+    }
 
-            // Map the event's window.
-            event.window = new_world_entity;
+    pub(crate) fn insert_occluded_event(
+        &mut self,
+        windows: &WinitWindows,
+        stamps: &mut WindowEventStamps,
+        event: WindowOccluded,
+    )
+    {
+        let Some(window_id) = windows.entity_to_winit.get(&event.window) else { return };
+        self.record(stamps, *window_id, CachedWindowEvent::Occluded(event));
+    }
+
+    pub(crate) fn insert_close_requested_event(
+        &mut self,
+        windows: &WinitWindows,
+        stamps: &mut WindowEventStamps,
+        event: WindowCloseRequested,
+    )
+    {
+        let Some(window_id) = windows.entity_to_winit.get(&event.window) else { return };
+        self.record(stamps, *window_id, CachedWindowEvent::CloseRequested(event));
+    }
 
-            // Forward to the new world.
-            new_world.send_event(event.clone());
-            new_world.send_event(WinitEvent::WindowScaleFactorChanged(event));
+    /// Appends another digest to this one.
+    ///
+    /// The other digest's events are treated as newer than this digest's events.
+    pub(crate) fn extend(&mut self, other: WindowEventCache)
+    {
+        for (window_id, event, stamp) in other.events {
+            self.insert(window_id, event, stamp);
         }
+    }
 
-        for (entity, mut event) in self.theme_events.drain() {
+    /// Sends all cached events to a world in order.
+    ///
+    /// Events the world already saw (e.g. because it collected them itself before a fork, then received them back
+    /// on join) are skipped.
+    pub(crate) fn dispatch(&mut self, windows: &WinitWindows, world: &mut World)
+    {
+        let mut stamps = world.remove_resource::<WindowEventStamps>().unwrap_or_default();
+
+        for (window_id, mut event, stamp) in self.events.drain(..) {
             // Drop events that don't have matching entities.
-            let Some(new_world_entity) = map_winit_window_entities(main_windows, new_windows, entity) else {
-                continue;
-            };
+            let Some(entity) = windows.winit_to_entity.get(&window_id) else { continue };
 
-            // Map the event's window.
-            event.window = new_world_entity;
+            // Drop events the world already saw.
+            if !stamps.receive(&event, window_id, stamp) {
+                continue;
+            }
 
-            // Forward to the new world.
-            new_world.send_event(event.clone());
-            new_world.send_event(WinitEvent::WindowThemeChanged(event));
+            // Map the event's window and forward it.
+            event.set_window(*entity);
+            event.send(world);
         }
+
+        world.insert_resource(stamps);
     }
 }
 
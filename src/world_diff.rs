@@ -0,0 +1,166 @@
+use std::any::TypeId;
+
+use bevy::prelude::*;
+use bevy::reflect::TypeRegistry;
+use bevy::utils::HashMap;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Marks a world that should record a [`ForkSnapshot`] when it is forked.
+#[derive(Resource, Default)]
+pub(crate) struct TakeForkSnapshot;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records a [`ForkSnapshot`] in a world that is being forked, if requested with
+/// [`WorldSwapApp::with_fork_snapshot`].
+pub(crate) fn take_fork_snapshot(world: &mut World)
+{
+    if world.remove_resource::<TakeForkSnapshot>().is_none() {
+        return;
+    }
+    let snapshot = ForkSnapshot::take(world);
+    world.insert_resource(snapshot);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Copies every reflected resource registered in a world's [`AppTypeRegistry`].
+fn reflect_resources(world: &World, registry: &TypeRegistry) -> HashMap<TypeId, Box<dyn Reflect>>
+{
+    registry
+        .iter()
+        .filter_map(|registration| {
+            let reflect_resource = registration.data::<ReflectResource>()?;
+            let value = reflect_resource.reflect(world)?;
+            Some((registration.type_id(), value.clone_value()))
+        })
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource with copies of a world's reflected resources, taken when the world was forked.
+///
+/// Request a snapshot with [`WorldSwapApp::with_fork_snapshot`]. Use [`ResourceDiff::since_fork`] when the world
+/// joins (e.g. in [`WorldSwapPlugin::swap_join_recovery`]) to find out which resources changed.
+///
+/// Only resources registered in the world's [`AppTypeRegistry`] with `#[reflect(Resource)]` are recorded.
+///
+/// Requires the `world_diff` feature.
+#[derive(Resource)]
+pub struct ForkSnapshot
+{
+    resources: HashMap<TypeId, Box<dyn Reflect>>,
+}
+
+impl ForkSnapshot
+{
+    /// Takes a snapshot of a world's reflected resources.
+    pub fn take(world: &World) -> Self
+    {
+        let Some(registry) = world.get_resource::<AppTypeRegistry>() else {
+            return Self { resources: HashMap::default() };
+        };
+        let registry = registry.read();
+        Self { resources: reflect_resources(world, &registry) }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reflected resources that changed in a world since a [`ForkSnapshot`] was taken.
+///
+/// Resources that always change (e.g. `Time`) will show up in every diff, so only look for the resources you want
+/// to merge into the parent world.
+///
+/// Requires the `world_diff` feature.
+pub struct ResourceDiff
+{
+    /// Current values of resources that changed or were added.
+    changed: HashMap<TypeId, Box<dyn Reflect>>,
+    /// Resources that were removed.
+    removed: Vec<TypeId>,
+}
+
+impl ResourceDiff
+{
+    /// Computes which reflected resources changed in a world since it was forked.
+    ///
+    /// Returns `None` if the world doesn't have a [`ForkSnapshot`].
+    ///
+    /// Resources whose values can't be compared with reflection are treated as changed.
+    pub fn since_fork(world: &World) -> Option<Self>
+    {
+        Some(Self::since(world, world.get_resource::<ForkSnapshot>()?))
+    }
+
+    /// Computes which reflected resources changed in a world since a snapshot was taken.
+    pub fn since(world: &World, snapshot: &ForkSnapshot) -> Self
+    {
+        let current = match world.get_resource::<AppTypeRegistry>() {
+            Some(registry) => reflect_resources(world, &registry.read()),
+            None => HashMap::default(),
+        };
+        let removed = snapshot
+            .resources
+            .keys()
+            .filter(|type_id| !current.contains_key(*type_id))
+            .copied()
+            .collect();
+        let changed = current
+            .into_iter()
+            .filter(|(type_id, value)| {
+                let Some(prev) = snapshot.resources.get(type_id) else { return true };
+                !value.reflect_partial_eq(prev.as_ref()).unwrap_or(false)
+            })
+            .collect();
+
+        Self { changed, removed }
+    }
+
+    /// Returns `true` if no resources changed.
+    pub fn is_empty(&self) -> bool
+    {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// Returns `true` if resource `R` changed or was added.
+    pub fn changed<R: Resource>(&self) -> bool
+    {
+        self.changed.contains_key(&TypeId::of::<R>())
+    }
+
+    /// Returns `true` if resource `R` was removed.
+    pub fn removed<R: Resource>(&self) -> bool
+    {
+        self.removed.contains(&TypeId::of::<R>())
+    }
+
+    /// Gets the current value of resource `R` if it changed or was added.
+    pub fn get<R: Resource + FromReflect>(&self) -> Option<R>
+    {
+        R::from_reflect(self.changed.get(&TypeId::of::<R>())?.as_ref())
+    }
+
+    /// Writes changed resources into another world, inserting them if missing.
+    ///
+    /// The other world must register the same resource types in its [`AppTypeRegistry`]. Removed resources are
+    /// not removed from the other world.
+    pub fn apply(&self, world: &mut World)
+    {
+        let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else { return };
+        let registry = registry.read();
+        for (type_id, value) in self.changed.iter() {
+            let Some(reflect_resource) = registry.get_type_data::<ReflectResource>(*type_id) else {
+                tracing::warn!("failed applying resource diff, {} is not registered", value.reflect_type_path());
+                continue;
+            };
+            reflect_resource.apply_or_insert(world, value.as_ref(), &registry);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use bevy_worldswap::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sends messages `1..=count` from a parent world to a child world, and returns the messages the child received.
+fn send_to_child(bound: ChannelBound, count: u32) -> Vec<u32>
+{
+    let mut parent = headless_app();
+    let mut child = headless_app();
+    WorldSwapChannels::<u32>::with_bound(bound).install(parent.world_mut(), child.world_mut());
+
+    let sender = parent.world().resource::<ChannelSender<u32>>();
+    for message in 1..=count {
+        sender.send(message);
+    }
+    child.update();
+
+    drain_events::<ChannelMessage<u32>>(&mut child)
+        .into_iter()
+        .map(|message| message.0)
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn unbounded_channels_keep_all_messages()
+{
+    assert_eq!(send_to_child(ChannelBound::Unbounded, 3), vec![1, 2, 3]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn drop_newest_drops_sent_messages()
+{
+    let bound = ChannelBound::Bounded { capacity: 2, overflow: OverflowPolicy::DropNewest };
+    assert_eq!(send_to_child(bound, 3), vec![1, 2]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn drop_oldest_drops_queued_messages()
+{
+    let bound = ChannelBound::Bounded { capacity: 2, overflow: OverflowPolicy::DropOldest };
+    assert_eq!(send_to_child(bound, 3), vec![2, 3]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn zero_capacity_holds_one_message()
+{
+    for overflow in [OverflowPolicy::DropNewest, OverflowPolicy::DropOldest] {
+        let bound = ChannelBound::Bounded { capacity: 0, overflow };
+        assert_eq!(send_to_child(bound, 3).len(), 1);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the ids and reasons of the swap errors sent to the app's world.
+fn swap_errors(app: &mut App) -> Vec<(SwapCommandId, SwapErrorReason)>
+{
+    drain_events::<SwapError>(app)
+        .into_iter()
+        .map(|error| (error.id, error.reason))
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn dropped_swap_commands_are_reported()
+{
+    let channel_bound = ChannelBound::Bounded { capacity: 1, overflow: OverflowPolicy::DropNewest };
+    let mut app = worldswap_app(WorldSwapPlugin { channel_bound, ..default() });
+    let sender = app.world().resource::<SwapCommandSender>().clone();
+    let first = sender.send(SwapCommand::Swap);
+    let second = sender.send(SwapCommand::Join);
+    app.update();
+
+    // There is no background world, so the command that was received fails too.
+    let errors = swap_errors(&mut app);
+    assert_eq!(errors.len(), 2);
+    assert!(errors.contains(&(first, SwapErrorReason::NoBackgroundWorld)));
+    assert!(errors.contains(&(second, SwapErrorReason::ChannelFull)));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn dropped_oldest_swap_commands_are_reported()
+{
+    let channel_bound = ChannelBound::Bounded { capacity: 1, overflow: OverflowPolicy::DropOldest };
+    let mut app = worldswap_app(WorldSwapPlugin { channel_bound, ..default() });
+    let sender = app.world().resource::<SwapCommandSender>().clone();
+    let first = sender.send(SwapCommand::Swap);
+    let second = sender.send(SwapCommand::Join);
+    app.update();
+
+    let errors = swap_errors(&mut app);
+    assert_eq!(errors.len(), 2);
+    assert!(errors.contains(&(first, SwapErrorReason::ChannelFull)));
+    assert!(errors.contains(&(second, SwapErrorReason::NoBackgroundWorld)));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use bevy_worldswap::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn arbitration_app(command_arbitration: CommandArbitration) -> App
+{
+    worldswap_app(WorldSwapPlugin { command_arbitration, ..default() })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the commands that were selected and applied.
+///
+/// There is no background world in these tests, so applied commands always fail with
+/// [`SwapErrorReason::NoBackgroundWorld`].
+fn applied_commands(app: &mut App) -> Vec<SwapCommandId>
+{
+    drain_events::<SwapError>(app)
+        .into_iter()
+        .filter(|error| error.reason == SwapErrorReason::NoBackgroundWorld)
+        .map(|error| error.id)
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn foreground_wins_applies_the_last_command()
+{
+    let mut app = arbitration_app(CommandArbitration::ForegroundWins);
+    let sender = app.world().resource::<SwapCommandSender>().clone();
+    sender.send(SwapCommand::Swap);
+    let last = sender.send(SwapCommand::Join);
+    app.update();
+    assert_eq!(applied_commands(&mut app), vec![last]);
+
+    // Discarded commands aren't applied later.
+    app.update();
+    assert!(applied_commands(&mut app).is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn first_wins_applies_the_first_command()
+{
+    let mut app = arbitration_app(CommandArbitration::FirstWins);
+    let sender = app.world().resource::<SwapCommandSender>().clone();
+    let first = sender.send(SwapCommand::Swap);
+    sender.send(SwapCommand::Join);
+    app.update();
+    assert_eq!(applied_commands(&mut app), vec![first]);
+
+    app.update();
+    assert!(applied_commands(&mut app).is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn queue_all_applies_one_command_per_tick()
+{
+    let mut app = arbitration_app(CommandArbitration::QueueAll);
+    let sender = app.world().resource::<SwapCommandSender>().clone();
+    let first = sender.send(SwapCommand::Swap);
+    let second = sender.send(SwapCommand::Join);
+    app.update();
+    assert_eq!(applied_commands(&mut app), vec![first]);
+
+    app.update();
+    assert_eq!(applied_commands(&mut app), vec![second]);
+
+    // Queued commands aren't superseded.
+    app.update();
+    assert!(drain_events::<SwapError>(&mut app).is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource, Default)]
+struct SystemRuns(usize);
+
+fn count_run(mut runs: ResMut<SystemRuns>)
+{
+    runs.0 += 1;
+}
+
+#[test]
+fn custom_commands_skip_arbitration()
+{
+    let mut app = worldswap_app(WorldSwapPlugin {
+        command_arbitration: CommandArbitration::FirstWins,
+        discard_policy: DiscardPolicy::ErrorEvent,
+        ..default()
+    });
+    app.init_resource::<SystemRuns>();
+    let sender = app.world().resource::<SwapCommandSender>().clone();
+    let custom_a = sender.run_in_foreground(count_run);
+    let swap = sender.send(SwapCommand::Swap);
+    let custom_b = sender.run_in_foreground(count_run);
+    app.update();
+
+    // All custom commands are applied, and they don't supersede the other command.
+    assert_eq!(app.world().resource::<SystemRuns>().0, 2);
+    let errors = drain_events::<SwapError>(&mut app);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].id, swap);
+    assert_eq!(errors[0].reason, SwapErrorReason::NoBackgroundWorld);
+    let applied: Vec<_> = drain_events::<SwapApplied>(&mut app)
+        .into_iter()
+        .map(|applied| applied.id)
+        .collect();
+    assert_eq!(applied, vec![custom_a, custom_b]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use bevy::winit::WinitWindows;
+use bevy_worldswap::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a headless app for tests.
+///
+/// The app gets an empty [`WinitWindows`] in place of the `WinitPlugin`, so its world can be swapped with other
+/// test worlds the same way windowed worlds are.
+pub fn headless_app() -> App
+{
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_non_send_resource::<WinitWindows>();
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a headless app with a [`WorldSwapPlugin`], ready to be updated.
+pub fn worldswap_app(plugin: WorldSwapPlugin) -> App
+{
+    let mut app = headless_app();
+    app.add_plugins(plugin);
+    app.finish();
+    app.cleanup();
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
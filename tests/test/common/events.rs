@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Drains the events of type `E` that were sent in the app's world.
+pub fn drain_events<E: Event>(app: &mut App) -> Vec<E>
+{
+    app.world_mut().resource_mut::<Events<E>>().drain().collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,3 +1,7 @@
 //module tree
+mod apps;
+mod events;
 
 //API exports
+pub use apps::*;
+pub use events::*;
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use bevy_worldswap::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes an app that applies the first command each tick and handles the others with `discard_policy`.
+fn discard_app(discard_policy: DiscardPolicy) -> App
+{
+    worldswap_app(WorldSwapPlugin {
+        command_arbitration: CommandArbitration::FirstWins,
+        discard_policy,
+        ..default()
+    })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the ids and reasons of the swap errors sent to the app's world.
+///
+/// There is no background world in these tests, so applied commands fail with
+/// [`SwapErrorReason::NoBackgroundWorld`].
+fn swap_errors(app: &mut App) -> Vec<(SwapCommandId, SwapErrorReason)>
+{
+    drain_events::<SwapError>(app)
+        .into_iter()
+        .map(|error| (error.id, error.reason))
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn warn_and_silent_discard_without_errors()
+{
+    for policy in [DiscardPolicy::Warn, DiscardPolicy::Silent] {
+        let mut app = discard_app(policy);
+        let sender = app.world().resource::<SwapCommandSender>().clone();
+        let first = sender.send(SwapCommand::Swap);
+        sender.send(SwapCommand::Join);
+        app.update();
+        assert_eq!(swap_errors(&mut app), vec![(first, SwapErrorReason::NoBackgroundWorld)]);
+
+        app.update();
+        assert!(swap_errors(&mut app).is_empty());
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn error_event_reports_superseded_commands()
+{
+    let mut app = discard_app(DiscardPolicy::ErrorEvent);
+    let sender = app.world().resource::<SwapCommandSender>().clone();
+    let first = sender.send(SwapCommand::Swap);
+    let second = sender.send(SwapCommand::Join);
+    app.update();
+
+    let errors = swap_errors(&mut app);
+    assert_eq!(errors.len(), 2);
+    assert!(errors.contains(&(first, SwapErrorReason::NoBackgroundWorld)));
+    assert!(errors.contains(&(second, SwapErrorReason::Superseded)));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn queue_applies_discarded_commands_later()
+{
+    let mut app = discard_app(DiscardPolicy::Queue);
+    let sender = app.world().resource::<SwapCommandSender>().clone();
+    let first = sender.send(SwapCommand::Swap);
+    let second = sender.send(SwapCommand::Join);
+    app.update();
+    assert_eq!(swap_errors(&mut app), vec![(first, SwapErrorReason::NoBackgroundWorld)]);
+
+    app.update();
+    assert_eq!(swap_errors(&mut app), vec![(second, SwapErrorReason::NoBackgroundWorld)]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,5 +1,12 @@
 //module tree
+mod channel_bound;
+mod command_arbitration;
 mod common;
+mod discard_policy;
+mod plugin_errors;
+mod registry;
+mod shared_store;
+mod swap_commands;
 
 //API exports
-//pub use common::*;
+pub use common::*;
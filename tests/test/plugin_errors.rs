@@ -0,0 +1,51 @@
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+use bevy_worldswap::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn try_add_adds_the_plugin()
+{
+    let mut app = headless_app();
+    assert_eq!(WorldSwapPlugin::default().try_add(&mut app), Ok(()));
+    assert!(app.is_plugin_added::<WorldSwapPlugin>());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn try_add_rejects_duplicate_plugins()
+{
+    let mut app = headless_app();
+    assert_eq!(WorldSwapPlugin::default().try_add(&mut app), Ok(()));
+    assert_eq!(WorldSwapPlugin::default().try_add(&mut app), Err(WorldSwapPluginError::DuplicatePlugin));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn try_add_rejects_apps_not_using_main()
+{
+    let mut app = headless_app();
+    app.main_mut().update_schedule = Some(Update.intern());
+    assert_eq!(WorldSwapPlugin::default().try_add(&mut app), Err(WorldSwapPluginError::MainScheduleNotMain));
+    assert!(!app.is_plugin_added::<WorldSwapPlugin>());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn try_add_rejects_blocking_channels()
+{
+    let mut app = headless_app();
+    let channel_bound = ChannelBound::Bounded { capacity: 1, overflow: OverflowPolicy::Block };
+    assert_eq!(
+        WorldSwapPlugin { channel_bound, ..default() }.try_add(&mut app),
+        Err(WorldSwapPluginError::BlockingChannelBound)
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
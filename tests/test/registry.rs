@@ -0,0 +1,53 @@
+use bevy_worldswap::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn forked_worlds_are_children_of_the_foreground()
+{
+    let mut app = worldswap_app(WorldSwapPlugin::default());
+    let initial = app.world().id();
+    let child = headless_app();
+    let child_id = child.world().id();
+
+    app.world()
+        .resource::<SwapCommandSender>()
+        .send(SwapCommand::Fork(WorldSwapApp::new(child)));
+    app.update();
+
+    let registry = app.world().resource::<WorldRegistry>();
+    assert_eq!(registry.foreground(), child_id);
+    assert_eq!(registry.background(), Some(initial));
+    assert_eq!(registry.parent(child_id), Some(initial));
+    assert_eq!(registry.parent(initial), None);
+    assert_eq!(registry.children(initial).collect::<Vec<_>>(), vec![child_id]);
+    assert!(registry.background_is_parent_of(child_id));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn swapping_keeps_parents()
+{
+    let mut app = worldswap_app(WorldSwapPlugin::default());
+    let initial = app.world().id();
+    let child = headless_app();
+    let child_id = child.world().id();
+
+    app.world()
+        .resource::<SwapCommandSender>()
+        .send(SwapCommand::Fork(WorldSwapApp::new(child)));
+    app.update();
+    app.world().resource::<SwapCommandSender>().send(SwapCommand::Swap);
+    app.update();
+
+    let registry = app.world().resource::<WorldRegistry>();
+    assert_eq!(registry.foreground(), initial);
+    assert_eq!(registry.background(), Some(child_id));
+    assert_eq!(registry.parent(child_id), Some(initial));
+    assert!(!registry.background_is_parent_of(child_id));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use bevy_worldswap::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn store_app() -> App
+{
+    let mut app = headless_app();
+    app.init_resource::<SharedStore>();
+    let plugin = SharedStorePlugin::new(app.world());
+    app.add_plugins(plugin);
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn clones_share_values()
+{
+    let store = SharedStore::default();
+    let clone = store.clone();
+    clone.insert(String::from("profile"));
+    assert!(store.contains::<String>());
+    assert_eq!(store.get::<String>().as_deref(), Some("profile"));
+
+    assert_eq!(store.remove::<String>().as_deref(), Some("profile"));
+    assert!(!clone.contains::<String>());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn modify_requires_an_existing_value()
+{
+    let store = SharedStore::default();
+    assert_eq!(store.modify(|value: &mut u32| *value += 1), None);
+
+    store.insert(1u32);
+    assert_eq!(store.modify(|value: &mut u32| *value += 1), Some(()));
+    assert_eq!(store.read(|value: &u32| *value), Some(2));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn changes_are_reported_once()
+{
+    let mut app = store_app();
+    let store = app.world().resource::<SharedStore>().clone();
+    app.update();
+    assert!(drain_events::<SharedStoreChanged>(&mut app).is_empty());
+
+    // Insert.
+    store.insert(1u32);
+    app.update();
+    let changes = drain_events::<SharedStoreChanged>(&mut app);
+    assert_eq!(changes.len(), 1);
+    assert!(changes[0].is::<u32>());
+
+    // No changes.
+    app.update();
+    assert!(drain_events::<SharedStoreChanged>(&mut app).is_empty());
+
+    // Modifying a missing value isn't a change.
+    store.modify(|value: &mut u64| *value += 1);
+    app.update();
+    assert!(drain_events::<SharedStoreChanged>(&mut app).is_empty());
+
+    // Modify.
+    store.modify(|value: &mut u32| *value += 1);
+    app.update();
+    let changes = drain_events::<SharedStoreChanged>(&mut app);
+    assert_eq!(changes.len(), 1);
+    assert!(changes[0].is::<u32>());
+
+    // Remove.
+    assert_eq!(store.remove::<u32>(), Some(2));
+    app.update();
+    let changes = drain_events::<SharedStoreChanged>(&mut app);
+    assert_eq!(changes.len(), 1);
+    assert!(changes[0].is::<u32>());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn plugin_falls_back_to_an_unshared_store()
+{
+    let mut app = headless_app();
+    let plugin = SharedStorePlugin::new(app.world());
+    app.add_plugins(plugin);
+    app.world().resource::<SharedStore>().insert(1u32);
+    app.update();
+    assert_eq!(drain_events::<SharedStoreChanged>(&mut app).len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,89 @@
+use bevy_worldswap::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn errors_report_the_failed_command()
+{
+    let mut app = worldswap_app(WorldSwapPlugin::default());
+    let id = app.world().resource::<SwapCommandSender>().send(SwapCommand::Swap);
+    app.update();
+
+    let errors = drain_events::<SwapError>(&mut app);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].id, id);
+    assert_eq!(errors[0].command, SwapCommandKind::Swap);
+    assert_eq!(errors[0].reason, SwapErrorReason::NoBackgroundWorld);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn applied_commands_are_reported_to_the_sender()
+{
+    let mut app = worldswap_app(WorldSwapPlugin::default());
+    let fork = app
+        .world()
+        .resource::<SwapCommandSender>()
+        .send(SwapCommand::Fork(WorldSwapApp::new(headless_app())));
+    app.update();
+
+    // The initial world is in the background now, so swap it back to read its events.
+    app.world().resource::<SwapCommandSender>().send(SwapCommand::Swap);
+    app.update();
+
+    let applied = drain_events::<SwapApplied>(&mut app);
+    assert_eq!(applied.len(), 1);
+    assert_eq!(applied[0].id, fork);
+    assert_eq!(applied[0].command, SwapCommandKind::Fork);
+    assert!(drain_events::<SwapError>(&mut app).is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn command_ids_are_unique_across_senders()
+{
+    let mut app = worldswap_app(WorldSwapPlugin::default());
+    let remote = app.world().resource::<WorldSwapRemoteHandle>().clone();
+    let fork = app
+        .world()
+        .resource::<SwapCommandSender>()
+        .send(SwapCommand::Fork(WorldSwapApp::new(headless_app())));
+    app.update();
+
+    // The child world's sender is made by the backend.
+    let swap = app.world().resource::<SwapCommandSender>().send(SwapCommand::Swap);
+    let join = remote.send(SwapCommand::Join);
+    assert_ne!(fork, swap);
+    assert_ne!(fork, join);
+    assert_ne!(swap, join);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn custom_commands_report_their_result()
+{
+    let mut app = worldswap_app(WorldSwapPlugin::default());
+    let sender = app.world().resource::<SwapCommandSender>().clone();
+    let applied = sender.send(SwapCommand::Custom(Box::new(|worlds| {
+        assert!(worlds.background.is_none());
+        Ok(())
+    })));
+    let failed = sender.run_in_background(|| ());
+    app.update();
+
+    let errors = drain_events::<SwapError>(&mut app);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].id, failed);
+    assert_eq!(errors[0].command, SwapCommandKind::Custom);
+    assert_eq!(errors[0].reason, SwapErrorReason::NoBackgroundWorld);
+    let applied_events = drain_events::<SwapApplied>(&mut app);
+    assert_eq!(applied_events.len(), 1);
+    assert_eq!(applied_events[0].id, applied);
+}
+
+//-------------------------------------------------------------------------------------------------------------------